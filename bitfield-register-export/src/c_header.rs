@@ -0,0 +1,200 @@
+// C header with CMSIS style defines and inline accessors, for C code sharing the register layouts:
+//
+//     #define SENSOR_CTRL_MODE_Pos 4U
+//     #define SENSOR_CTRL_MODE_Msk (0x3UL << SENSOR_CTRL_MODE_Pos)
+//     static inline uint16_t SENSOR_CTRL_MODE_Get(uint16_t reg) { ... }
+//     static inline uint16_t SENSOR_CTRL_MODE_Set(uint16_t reg, uint16_t value) { ... }
+//
+// names are prefixed with the peripheral, so registers of different peripherals may share a name
+use super::{address_offset, owned_fields, register_reset, Device, Peripheral};
+use bitfield_register::{Access, FieldDescription, RegisterDescription};
+
+// Ctrl, CountValue, en -> CTRL, COUNT_VALUE, EN
+pub fn upper_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !result.ends_with('_') {
+                result.push('_');
+            }
+        } else {
+            if c.is_ascii_uppercase() && previous.map_or(false, |p| p.is_ascii_lowercase()) {
+                result.push('_');
+            }
+            result.push(c.to_ascii_uppercase());
+        }
+        previous = Some(c);
+    }
+    return result.trim_matches('_').to_string();
+}
+
+// registers wider than 64 bits have no C type, only positions and widths of their fields are defined
+fn c_type(size: usize) -> Option<&'static str> {
+    return match size {
+        1 => Some("uint8_t"),
+        2 => Some("uint16_t"),
+        3..=4 => Some("uint32_t"),
+        5..=8 => Some("uint64_t"),
+        _ => None,
+    };
+}
+
+fn comment_text(text: &str) -> String {
+    return text.replace("*/", "* /").replace('\n', " ");
+}
+
+// "/* Ctrl: Sensor control */"
+fn section(name: &str, description: Option<&str>) -> String {
+    return match description {
+        Some(description) => format!("/* {}: {} */", name, comment_text(description)),
+        None => format!("/* {} */", name),
+    };
+}
+
+fn define(lines: &mut Vec<String>, name: &str, value: &str) {
+    lines.push(format!("#define {:<32} {}", name, value));
+}
+
+pub fn to_c_header(device: &Device) -> String {
+    let guard = format!("{}_H", upper_snake_case(&device.name));
+
+    let mut lines = vec![];
+    lines.push(format!("/* Generated by bitfield-register-export from {}, do not edit. */", device.name));
+    lines.push(format!("#ifndef {}", guard));
+    lines.push(format!("#define {}", guard));
+    lines.push(String::new());
+    lines.push(String::from("#include <stdint.h>"));
+
+    for peripheral in &device.peripherals {
+        peripheral_header(&mut lines, peripheral);
+    }
+
+    lines.push(String::new());
+    lines.push(format!("#endif /* {} */", guard));
+    lines.push(String::new());
+    return lines.join("\n");
+}
+
+fn peripheral_header(lines: &mut Vec<String>, peripheral: &Peripheral) {
+    let prefix = upper_snake_case(&peripheral.name);
+
+    lines.push(String::new());
+    lines.push(section(&peripheral.name, peripheral.description.as_ref().map(|description| description.as_str())));
+    define(lines, &format!("{}_BASE", prefix), &format!("0x{:08X}UL", peripheral.base_address));
+
+    for register in &peripheral.registers {
+        register_header(lines, &prefix, register);
+    }
+}
+
+fn register_header(lines: &mut Vec<String>, prefix: &str, register: &RegisterDescription) {
+    let name = format!("{}_{}", prefix, upper_snake_case(register.name));
+    let ty = c_type(register.size);
+    let suffix = if register.size > 4 { "ULL" } else { "UL" };
+
+    lines.push(String::new());
+    lines.push(section(register.name, register.description));
+    define(lines, &format!("{}_OFFSET", name), &format!("0x{:X}UL", address_offset(register)));
+    define(lines, &format!("{}_ADDR", name), &format!("({}_BASE + {}_OFFSET)", prefix, name));
+    define(lines, &format!("{}_SIZE", name), &format!("{}U", register.size));
+    if ty.is_some() {
+        define(lines, &format!("{}_RESET", name), &format!("{}{}", register_reset(register), suffix));
+    }
+
+    // one set of defines per bit, aliases are left out
+    for (field_name, field) in owned_fields(register) {
+        let field_name = format!("{}_{}", name, upper_snake_case(&field_name));
+        if let Some(description) = field.description {
+            lines.push(format!("/* {} */", comment_text(description)));
+        }
+        define(lines, &format!("{}_Pos", field_name), &format!("{}U", field.first_bit));
+        define(lines, &format!("{}_Width", field_name), &format!("{}U", field.bit_width()));
+        if let Some(ty) = ty {
+            field_accessors(lines, &field_name, ty, suffix, &field, split_piece(register, &field));
+        }
+    }
+}
+
+// piece of a split field, flat_fields names it <field>_<i>
+fn split_piece(register: &RegisterDescription, piece: &FieldDescription) -> bool {
+    return register.fields.iter().any(|field| {
+        field.is_split() && !field.alias && field.ranges.iter().any(|&(first_bit, _)| first_bit == piece.first_bit)
+    });
+}
+
+// C expression with the low bytes of value in reverse order
+fn swap_bytes(value: &str, bytes: usize, suffix: &str) -> String {
+    let parts: Vec<String> = (0..bytes).map(|i| {
+        let byte = if i == 0 { format!("({} & 0xFF{})", value, suffix) } else { format!("(({} >> {}U) & 0xFF{})", value, 8 * i, suffix) };
+        let shift = 8 * (bytes - 1 - i);
+        return if shift == 0 { byte } else { format!("({} << {}U)", byte, shift) };
+    }).collect();
+    return parts.join(" | ");
+}
+
+fn field_accessors(lines: &mut Vec<String>, name: &str, ty: &str, suffix: &str, field: &FieldDescription, split_piece: bool) {
+    let max = if field.bit_width() == 64 { u64::max_value() } else { (1u64 << field.bit_width()) - 1 };
+    define(lines, &format!("{}_Msk", name), &format!("(0x{:X}{} << {}_Pos)", max, suffix, name));
+
+    // a piece holds bytes of a value that only makes sense with the other pieces, C code has to assemble them
+    if field.big_endian && split_piece {
+        lines.push(format!("/* {}: piece of a big endian field, no accessors */", name));
+        return;
+    }
+
+    // big endian fields are stored most significant byte first, the accessors swap them like the rust ones
+    let bytes = field.bit_width() / 8;
+    if field.big_endian && bytes > 1 {
+        if field.access != Access::WriteOnly {
+            lines.push(format!(
+                "static inline {ty} {name}_Get({ty} reg) {{ {ty} raw = ({ty})((reg & {name}_Msk) >> {name}_Pos); return ({ty})({swap}); }}",
+                ty = ty, name = name, swap = swap_bytes("raw", bytes, suffix)
+            ));
+        }
+        if field.access != Access::ReadOnly {
+            lines.push(format!(
+                "static inline {ty} {name}_Set({ty} reg, {ty} value) {{ {ty} raw = ({ty})({swap}); return ({ty})((reg & ~{name}_Msk) | ((raw << {name}_Pos) & {name}_Msk)); }}",
+                ty = ty, name = name, swap = swap_bytes("value", bytes, suffix)
+            ));
+        }
+        return;
+    }
+
+    // same accessors as the rust register: no getter for write-only, no setter for read-only fields
+    if field.access != Access::WriteOnly {
+        lines.push(format!(
+            "static inline {ty} {name}_Get({ty} reg) {{ return ({ty})((reg & {name}_Msk) >> {name}_Pos); }}",
+            ty = ty, name = name
+        ));
+    }
+    if field.access != Access::ReadOnly {
+        lines.push(format!(
+            "static inline {ty} {name}_Set({ty} reg, {ty} value) {{ return ({ty})((reg & ~{name}_Msk) | ((({ty})value << {name}_Pos) & {name}_Msk)); }}",
+            ty = ty, name = name
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upper_snake_case_test() {
+        assert_eq!(upper_snake_case("Ctrl"), "CTRL");
+        assert_eq!(upper_snake_case("CountValue"), "COUNT_VALUE");
+        assert_eq!(upper_snake_case("rx_data"), "RX_DATA");
+        assert_eq!(upper_snake_case("SENSOR_DEVICE"), "SENSOR_DEVICE");
+        assert_eq!(upper_snake_case("Timer0"), "TIMER0");
+    }
+
+    #[test]
+    fn swap_bytes_test() {
+        assert_eq!(swap_bytes("raw", 2, "UL"), "((raw & 0xFFUL) << 8U) | ((raw >> 8U) & 0xFFUL)");
+        assert_eq!(
+            swap_bytes("raw", 3, "UL"),
+            "((raw & 0xFFUL) << 16U) | (((raw >> 8U) & 0xFFUL) << 8U) | ((raw >> 16U) & 0xFFUL)"
+        );
+    }
+}
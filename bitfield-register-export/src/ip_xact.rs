@@ -0,0 +1,81 @@
+// IEEE 1685-2014 IP-XACT component, one memory map per device and one address block per peripheral
+use super::{access_name, address_offset, field_reset, owned_fields, register_access, Device, Peripheral, XmlWriter};
+use bitfield_register::RegisterDescription;
+
+pub fn to_ip_xact(device: &Device) -> String {
+    let mut xml = XmlWriter::new("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    xml.open("ipxact:component xmlns:ipxact=\"http://www.accellera.org/XMLSchema/IPXACT/1685-2014\"");
+    xml.element("ipxact:vendor", &device.vendor);
+    xml.element("ipxact:library", "registers");
+    xml.element("ipxact:name", &device.name);
+    xml.element("ipxact:version", &device.version);
+
+    xml.open("ipxact:memoryMaps");
+    xml.open("ipxact:memoryMap");
+    xml.element("ipxact:name", &device.name);
+    if let Some(ref description) = device.description {
+        xml.element("ipxact:description", description);
+    }
+    for peripheral in &device.peripherals {
+        address_block(&mut xml, peripheral);
+    }
+    xml.element("ipxact:addressUnitBits", "8");
+    xml.close("ipxact:memoryMap");
+    xml.close("ipxact:memoryMaps");
+
+    xml.close("ipxact:component");
+    return xml.finish();
+}
+
+fn address_block(xml: &mut XmlWriter, peripheral: &Peripheral) {
+    xml.open("ipxact:addressBlock");
+    xml.element("ipxact:name", &peripheral.name);
+    if let Some(ref description) = peripheral.description {
+        xml.element("ipxact:description", description);
+    }
+    xml.element("ipxact:baseAddress", &format!("'h{:X}", peripheral.base_address));
+    xml.element("ipxact:range", &peripheral.size().to_string());
+    xml.element("ipxact:width", "32");
+    xml.element("ipxact:usage", "register");
+
+    for register in &peripheral.registers {
+        register_ip_xact(xml, register);
+    }
+    xml.close("ipxact:addressBlock");
+}
+
+// ipxact numbers use verilog style hex, 'hFF
+fn verilog_hex(hex: &str) -> String {
+    return format!("'h{}", hex.trim_start_matches("0x"));
+}
+
+fn register_ip_xact(xml: &mut XmlWriter, register: &RegisterDescription) {
+    xml.open("ipxact:register");
+    xml.element("ipxact:name", register.name);
+    if let Some(description) = register.description {
+        xml.element("ipxact:description", description);
+    }
+    xml.element("ipxact:addressOffset", &format!("'h{:X}", address_offset(register)));
+    xml.element("ipxact:size", &(register.size * 8).to_string());
+    xml.element("ipxact:access", access_name(register_access(register)));
+
+    // IP-XACT fields may not overlap, aliases are left out
+    for (name, field) in owned_fields(register) {
+        xml.open("ipxact:field");
+        xml.element("ipxact:name", &name);
+        if let Some(description) = field.description {
+            xml.element("ipxact:description", description);
+        }
+        xml.element("ipxact:bitOffset", &field.first_bit.to_string());
+        xml.open("ipxact:resets");
+        xml.open("ipxact:reset");
+        xml.element("ipxact:value", &verilog_hex(&field_reset(register, &field)));
+        xml.close("ipxact:reset");
+        xml.close("ipxact:resets");
+        xml.element("ipxact:bitWidth", &field.bit_width().to_string());
+        xml.element("ipxact:access", access_name(field.access));
+        xml.close("ipxact:field");
+    }
+
+    xml.close("ipxact:register");
+}
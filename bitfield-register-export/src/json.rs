@@ -0,0 +1,100 @@
+// JSON described by schema.json in the crate root
+use super::{access_name, address_offset, flat_fields, register_access, register_reset, Device};
+
+fn string(text: &str) -> String {
+    let mut result = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    return result;
+}
+
+fn optional_string(text: Option<&str>) -> String {
+    return match text {
+        Some(text) => string(text),
+        None => String::from("null"),
+    };
+}
+
+fn indent(level: usize) -> String {
+    return "  ".repeat(level);
+}
+
+// members of an object or items of an array, one per line
+fn join(items: Vec<String>, level: usize, open: &str, close: &str) -> String {
+    if items.is_empty() {
+        return format!("{}{}", open, close);
+    }
+    let items: Vec<String> = items.iter().map(|item| format!("{}{}", indent(level + 1), item)).collect();
+    return format!("{}\n{}\n{}{}", open, items.join(",\n"), indent(level), close);
+}
+
+fn object(members: Vec<(&str, String)>, level: usize) -> String {
+    let members = members.into_iter().map(|(name, value)| format!("{}: {}", string(name), value)).collect();
+    return join(members, level, "{", "}");
+}
+
+pub fn to_json(device: &Device) -> String {
+    let peripherals = device.peripherals.iter().map(|peripheral| {
+        let registers = peripheral.registers.iter().map(|register| {
+            let fields = flat_fields(register).into_iter().map(|(name, field)| {
+                return object(vec![
+                    ("name", string(&name)),
+                    ("description", optional_string(field.description)),
+                    ("bitOffset", field.first_bit.to_string()),
+                    ("bitWidth", field.bit_width().to_string()),
+                    ("access", string(access_name(field.access))),
+                    ("alias", field.alias.to_string()),
+                    ("bigEndian", field.big_endian.to_string()),
+                ], 6);
+            }).collect();
+
+            return object(vec![
+                ("name", string(register.name)),
+                ("description", optional_string(register.description)),
+                ("addressOffset", address_offset(register).to_string()),
+                ("size", (register.size * 8).to_string()),
+                ("access", string(access_name(register_access(register)))),
+                ("resetValue", string(&register_reset(register))),
+                ("fields", join(fields, 5, "[", "]")),
+            ], 4);
+        }).collect();
+
+        return object(vec![
+            ("name", string(&peripheral.name)),
+            ("description", optional_string(peripheral.description.as_ref().map(|description| description.as_str()))),
+            ("baseAddress", peripheral.base_address.to_string()),
+            ("registers", join(registers, 3, "[", "]")),
+        ], 2);
+    }).collect();
+
+    let device = object(vec![
+        ("name", string(&device.name)),
+        ("description", optional_string(device.description.as_ref().map(|description| description.as_str()))),
+        ("vendor", string(&device.vendor)),
+        ("version", string(&device.version)),
+        ("peripherals", join(peripherals, 1, "[", "]")),
+    ], 0);
+    return format!("{}\n", device);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_test() {
+        assert_eq!(string("plain"), "\"plain\"");
+        assert_eq!(string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+        assert_eq!(string("\u{1}"), "\"\\u0001\"");
+    }
+}
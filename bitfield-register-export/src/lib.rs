@@ -0,0 +1,239 @@
+// Exports #[register] layouts to CMSIS-SVD, IP-XACT, JSON and C headers.
+//
+// Layouts come from DescribedRegister::DESCRIPTION, emitted by the macro for every register.
+// Registers are listed by hand, usually in a small binary or test of the driver crate:
+//
+//     let device = Device::new("SENSOR")
+//         .peripheral(Peripheral::new("SENSOR", 0).register::<Ctrl>().register::<Status>());
+//     fs::write("sensor.svd", svd::to_svd(&device))?;
+
+extern crate bitfield_register;
+
+use bitfield_register::{Access, DescribedRegister, FieldDescription, RegisterDescription};
+
+pub mod c_header;
+pub mod ip_xact;
+pub mod json;
+pub mod svd;
+
+pub struct Peripheral {
+    pub name: String,
+    pub description: Option<String>,
+    pub base_address: u32,
+    // register address is the offset from base_address, registers without address are placed at 0
+    pub registers: Vec<RegisterDescription>,
+}
+
+impl Peripheral {
+    pub fn new(name: &str, base_address: u32) -> Self {
+        return Peripheral { name: name.to_string(), description: None, base_address, registers: vec![] };
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        return self;
+    }
+
+    // registers are not collected automatically, every register of the peripheral has to be added here
+    pub fn register<R: DescribedRegister>(mut self) -> Self {
+        self.registers.push(R::DESCRIPTION);
+        return self;
+    }
+
+    // bytes from base_address to the end of the last register
+    pub fn size(&self) -> u64 {
+        return self.registers
+            .iter()
+            .map(|register| address_offset(register) as u64 + register.size as u64)
+            .max()
+            .unwrap_or(0);
+    }
+}
+
+pub struct Device {
+    pub name: String,
+    pub description: Option<String>,
+    pub vendor: String,
+    pub version: String,
+    pub peripherals: Vec<Peripheral>,
+}
+
+impl Device {
+    pub fn new(name: &str) -> Self {
+        return Device {
+            name: name.to_string(),
+            description: None,
+            vendor: String::from("unknown"),
+            version: String::from("1.0"),
+            peripherals: vec![],
+        };
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        return self;
+    }
+
+    pub fn vendor(mut self, vendor: &str) -> Self {
+        self.vendor = vendor.to_string();
+        return self;
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.to_string();
+        return self;
+    }
+
+    pub fn peripheral(mut self, peripheral: Peripheral) -> Self {
+        self.peripherals.push(peripheral);
+        return self;
+    }
+}
+
+pub fn address_offset(register: &RegisterDescription) -> u32 {
+    return register.address.unwrap_or(0);
+}
+
+// names used by both SVD and IP-XACT
+pub fn access_name(access: Access) -> &'static str {
+    return match access {
+        Access::ReadWrite => "read-write",
+        Access::ReadOnly => "read-only",
+        Access::WriteOnly => "write-only",
+    };
+}
+
+// read-only or write-only when every field is, read-write otherwise
+pub fn register_access(register: &RegisterDescription) -> Access {
+    let all = |access: Access| register.fields.iter().all(|field| field.access == access);
+    if !register.fields.is_empty() && all(Access::ReadOnly) {
+        return Access::ReadOnly;
+    }
+    if !register.fields.is_empty() && all(Access::WriteOnly) {
+        return Access::WriteOnly;
+    }
+    return Access::ReadWrite;
+}
+
+// bits [first_bit, first_bit + bit_width) of bytes, byte 0 holds bits 0..8
+fn extract_bits(bytes: &[u8], first_bit: usize, bit_width: usize) -> Vec<u8> {
+    let mut result = vec![0u8; (bit_width + 7) / 8];
+    for i in 0..bit_width {
+        let bit = first_bit + i;
+        if bit / 8 < bytes.len() && bytes[bit / 8] & (1 << (bit % 8)) != 0 {
+            result[i / 8] |= 1 << (i % 8);
+        }
+    }
+    return result;
+}
+
+// 0x-prefixed, most significant byte first
+fn hex(bytes: &[u8]) -> String {
+    let mut result = String::from("0x");
+    for byte in bytes.iter().rev() {
+        result.push_str(&format!("{:02X}", byte));
+    }
+    return result;
+}
+
+// fields as the formats know them: contiguous bit ranges
+// a split field becomes one field per piece, named <field>_0, <field>_1, ... least significant first
+pub fn flat_fields(register: &RegisterDescription) -> Vec<(String, FieldDescription)> {
+    let mut result = vec![];
+    for field in register.fields.iter() {
+        if !field.is_split() {
+            result.push((field.name.to_string(), *field));
+            continue;
+        }
+        for (i, &(first_bit, last_bit)) in field.ranges.iter().enumerate() {
+            let piece = FieldDescription { first_bit, last_bit, ranges: &field.ranges[i..i + 1], ..*field };
+            result.push((format!("{}_{}", field.name, i), piece));
+        }
+    }
+    return result;
+}
+
+// flat_fields without aliases, so every bit is described once
+pub fn owned_fields(register: &RegisterDescription) -> Vec<(String, FieldDescription)> {
+    return flat_fields(register).into_iter().filter(|&(_, ref field)| !field.alias).collect();
+}
+
+pub fn register_reset(register: &RegisterDescription) -> String {
+    return hex(register.reset);
+}
+
+pub fn field_reset(register: &RegisterDescription, field: &FieldDescription) -> String {
+    return hex(&extract_bits(register.reset, field.first_bit, field.bit_width()));
+}
+
+fn escape_xml(text: &str) -> String {
+    return text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;");
+}
+
+// indented xml, one element per line
+struct XmlWriter {
+    text: String,
+    indent: usize,
+}
+
+impl XmlWriter {
+    fn new(header: &str) -> Self {
+        return XmlWriter { text: format!("{}\n", header), indent: 0 };
+    }
+
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.indent {
+            self.text.push_str("  ");
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.line(&format!("<{}>", tag));
+        self.indent += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.indent -= 1;
+        let name = tag.split_whitespace().next().unwrap();
+        self.line(&format!("</{}>", name));
+    }
+
+    fn element(&mut self, tag: &str, value: &str) {
+        self.line(&format!("<{}>{}</{}>", tag, escape_xml(value), tag));
+    }
+
+    fn finish(self) -> String {
+        return self.text;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_bits_test() {
+        assert_eq!(extract_bits(&[0b10110100], 2, 4), vec![0b1101]);
+        assert_eq!(extract_bits(&[0b11000000, 0b00000011], 6, 4), vec![0b1111]);
+        assert_eq!(extract_bits(&[0xFF, 0x12, 0x34], 8, 16), vec![0x12, 0x34]);
+        assert_eq!(extract_bits(&[0xFF], 4, 8), vec![0x0F]);
+    }
+
+    #[test]
+    fn hex_test() {
+        assert_eq!(hex(&[0x00]), "0x00");
+        assert_eq!(hex(&[0x34, 0x12]), "0x1234");
+        assert_eq!(hex(&[]), "0x");
+    }
+
+    #[test]
+    fn escape_xml_test() {
+        assert_eq!(escape_xml("a < b & \"c\" > d"), "a &lt; b &amp; &quot;c&quot; &gt; d");
+    }
+}
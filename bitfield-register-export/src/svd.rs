@@ -0,0 +1,75 @@
+// CMSIS-SVD 1.3, readable by bitfield-register-svd and vendor tools
+use super::{access_name, address_offset, owned_fields, register_access, register_reset, Device, Peripheral, XmlWriter};
+use bitfield_register::RegisterDescription;
+
+pub fn to_svd(device: &Device) -> String {
+    let mut xml = XmlWriter::new("<?xml version=\"1.0\" encoding=\"utf-8\"?>");
+    xml.open("device schemaVersion=\"1.3\" xmlns:xs=\"http://www.w3.org/2001/XMLSchema-instance\" xs:noNamespaceSchemaLocation=\"CMSIS-SVD.xsd\"");
+    xml.element("vendor", &device.vendor);
+    xml.element("name", &device.name);
+    xml.element("version", &device.version);
+    xml.element("description", device.description.as_ref().map_or(device.name.as_str(), |description| description.as_str()));
+    xml.element("addressUnitBits", "8");
+    xml.element("width", "32");
+
+    xml.open("peripherals");
+    for peripheral in &device.peripherals {
+        peripheral_svd(&mut xml, peripheral);
+    }
+    xml.close("peripherals");
+
+    xml.close("device");
+    return xml.finish();
+}
+
+fn peripheral_svd(xml: &mut XmlWriter, peripheral: &Peripheral) {
+    xml.open("peripheral");
+    xml.element("name", &peripheral.name);
+    if let Some(ref description) = peripheral.description {
+        xml.element("description", description);
+    }
+    xml.element("baseAddress", &format!("0x{:08X}", peripheral.base_address));
+
+    xml.open("addressBlock");
+    xml.element("offset", "0x0");
+    xml.element("size", &format!("0x{:X}", peripheral.size()));
+    xml.element("usage", "registers");
+    xml.close("addressBlock");
+
+    xml.open("registers");
+    for register in &peripheral.registers {
+        register_svd(xml, register);
+    }
+    xml.close("registers");
+
+    xml.close("peripheral");
+}
+
+fn register_svd(xml: &mut XmlWriter, register: &RegisterDescription) {
+    xml.open("register");
+    xml.element("name", register.name);
+    if let Some(description) = register.description {
+        xml.element("description", description);
+    }
+    xml.element("addressOffset", &format!("0x{:X}", address_offset(register)));
+    xml.element("size", &(register.size * 8).to_string());
+    xml.element("access", access_name(register_access(register)));
+    xml.element("resetValue", &register_reset(register));
+
+    xml.open("fields");
+    // SVD fields may not overlap, aliases are left out
+    for (name, field) in owned_fields(register) {
+        xml.open("field");
+        xml.element("name", &name);
+        if let Some(description) = field.description {
+            xml.element("description", description);
+        }
+        xml.element("bitOffset", &field.first_bit.to_string());
+        xml.element("bitWidth", &field.bit_width().to_string());
+        xml.element("access", access_name(field.access));
+        xml.close("field");
+    }
+    xml.close("fields");
+
+    xml.close("register");
+}
@@ -0,0 +1,137 @@
+extern crate bitfield_register;
+extern crate bitfield_register_export;
+extern crate bitfield_register_macro;
+extern crate bitfield_register_svd;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use bitfield_register_export::{c_header, ip_xact, json, svd, Device, Peripheral};
+use bitfield_register_macro::register;
+
+/// Sensor control
+#[register(address = 0x00, size = 2, reset = 0x0310)]
+pub struct Ctrl {
+    /// Enable measurements
+    #[bitfield(at = 0)]
+    en: bool,
+    #[bitfield(from = 4, to = 5)]
+    mode: u8,
+    /// Samples to average
+    #[bitfield(from = 8, to = 11)]
+    average: u8,
+    /// Mode and average as one value
+    #[bitfield(from = 4, to = 11, overlay)]
+    config: u8,
+}
+
+/// Sensor status
+#[register(address = 0x04, reset = 0x80)]
+pub struct Status {
+    #[bitfield(at = 0, access = "read-only")]
+    ready: bool,
+    #[bitfield(at = 7, access = "read-only")]
+    idle: bool,
+}
+
+#[register(address = 0x08, size = 4)]
+pub struct Command {
+    #[bitfield(from = 0, to = 15, access = "write-only")]
+    code: u16,
+    /// Unlock key
+    #[bitfield(bits = [16..=19, 24..=27], access = "write-only")]
+    key: u8,
+}
+
+/// Last measurement
+#[register(address = 0x0C, size = 4)]
+pub struct Sample {
+    /// Raw reading, most significant byte first
+    #[bitfield(from = 0, to = 15, endian = "big")]
+    reading: u16,
+    #[bitfield(from = 16, to = 19)]
+    channel: u8,
+}
+
+fn device() -> Device {
+    let sensor = Peripheral::new("SENSOR", 0x40010000)
+        .description("Temperature & humidity sensor")
+        .register::<Ctrl>()
+        .register::<Status>()
+        .register::<Command>()
+        .register::<Sample>();
+    return Device::new("SENSOR_DEVICE").vendor("kviver").peripheral(sensor);
+}
+
+fn fixture(name: &str) -> PathBuf {
+    return PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(name);
+}
+
+// compares exported text with fixtures/<name>, UPDATE_GOLDEN=1 rewrites the expected files
+fn check_golden(name: &str, exported: &str) {
+    let golden = fixture(name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden, exported).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&golden).unwrap();
+    assert_eq!(exported, expected, "exported text differs from {}", golden.display());
+}
+
+#[test]
+fn svd_test() {
+    check_golden("sensor.svd", &svd::to_svd(&device()));
+}
+
+#[test]
+fn ip_xact_test() {
+    check_golden("sensor.xml", &ip_xact::to_ip_xact(&device()));
+}
+
+#[test]
+fn json_test() {
+    check_golden("sensor.json", &json::to_json(&device()));
+}
+
+#[test]
+fn c_header_test() {
+    check_golden("sensor.h", &c_header::to_c_header(&device()));
+}
+
+#[test]
+fn svd_round_trip_test() {
+    use bitfield_register_svd::svd::{parse_device, Access};
+
+    let device = parse_device(&svd::to_svd(&device())).unwrap();
+    assert_eq!(device.name, "SENSOR_DEVICE");
+    assert_eq!(device.peripherals.len(), 1);
+
+    let peripheral = &device.peripherals[0];
+    assert_eq!(peripheral.base_address, 0x40010000);
+    assert_eq!(peripheral.description.as_ref().unwrap(), "Temperature & humidity sensor");
+    assert_eq!(peripheral.registers.len(), 4);
+
+    let ctrl = &peripheral.registers[0];
+    assert_eq!(ctrl.name, "Ctrl");
+    assert_eq!(ctrl.description.as_ref().unwrap(), "Sensor control");
+    assert_eq!(ctrl.address_offset, 0);
+    assert_eq!(ctrl.size, 16);
+    assert_eq!(ctrl.reset_value, 0x0310);
+    let fields: Vec<(&str, u32, u32)> = ctrl.fields.iter().map(|field| (field.name.as_str(), field.bit_offset, field.bit_width)).collect();
+    assert_eq!(fields, vec![("en", 0, 1), ("mode", 4, 2), ("average", 8, 4)]);
+
+    let status = &peripheral.registers[1];
+    assert_eq!(status.address_offset, 4);
+    assert_eq!(status.size, 8);
+    assert_eq!(status.access, Access::ReadOnly);
+    assert_eq!(status.reset_value, 0x80);
+
+    let command = &peripheral.registers[2];
+    assert_eq!(command.size, 32);
+    assert_eq!(command.access, Access::WriteOnly);
+    assert_eq!(command.fields[0].access, Access::WriteOnly);
+    let fields: Vec<(&str, u32, u32)> = command.fields.iter().map(|field| (field.name.as_str(), field.bit_offset, field.bit_width)).collect();
+    assert_eq!(fields, vec![("code", 0, 16), ("key_0", 16, 4), ("key_1", 24, 4)]);
+}
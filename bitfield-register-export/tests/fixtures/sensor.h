@@ -0,0 +1,84 @@
+/* Generated by bitfield-register-export from SENSOR_DEVICE, do not edit. */
+#ifndef SENSOR_DEVICE_H
+#define SENSOR_DEVICE_H
+
+#include <stdint.h>
+
+/* SENSOR: Temperature & humidity sensor */
+#define SENSOR_BASE                      0x40010000UL
+
+/* Ctrl: Sensor control */
+#define SENSOR_CTRL_OFFSET               0x0UL
+#define SENSOR_CTRL_ADDR                 (SENSOR_BASE + SENSOR_CTRL_OFFSET)
+#define SENSOR_CTRL_SIZE                 2U
+#define SENSOR_CTRL_RESET                0x0310UL
+/* Enable measurements */
+#define SENSOR_CTRL_EN_Pos               0U
+#define SENSOR_CTRL_EN_Width             1U
+#define SENSOR_CTRL_EN_Msk               (0x1UL << SENSOR_CTRL_EN_Pos)
+static inline uint16_t SENSOR_CTRL_EN_Get(uint16_t reg) { return (uint16_t)((reg & SENSOR_CTRL_EN_Msk) >> SENSOR_CTRL_EN_Pos); }
+static inline uint16_t SENSOR_CTRL_EN_Set(uint16_t reg, uint16_t value) { return (uint16_t)((reg & ~SENSOR_CTRL_EN_Msk) | (((uint16_t)value << SENSOR_CTRL_EN_Pos) & SENSOR_CTRL_EN_Msk)); }
+#define SENSOR_CTRL_MODE_Pos             4U
+#define SENSOR_CTRL_MODE_Width           2U
+#define SENSOR_CTRL_MODE_Msk             (0x3UL << SENSOR_CTRL_MODE_Pos)
+static inline uint16_t SENSOR_CTRL_MODE_Get(uint16_t reg) { return (uint16_t)((reg & SENSOR_CTRL_MODE_Msk) >> SENSOR_CTRL_MODE_Pos); }
+static inline uint16_t SENSOR_CTRL_MODE_Set(uint16_t reg, uint16_t value) { return (uint16_t)((reg & ~SENSOR_CTRL_MODE_Msk) | (((uint16_t)value << SENSOR_CTRL_MODE_Pos) & SENSOR_CTRL_MODE_Msk)); }
+/* Samples to average */
+#define SENSOR_CTRL_AVERAGE_Pos          8U
+#define SENSOR_CTRL_AVERAGE_Width        4U
+#define SENSOR_CTRL_AVERAGE_Msk          (0xFUL << SENSOR_CTRL_AVERAGE_Pos)
+static inline uint16_t SENSOR_CTRL_AVERAGE_Get(uint16_t reg) { return (uint16_t)((reg & SENSOR_CTRL_AVERAGE_Msk) >> SENSOR_CTRL_AVERAGE_Pos); }
+static inline uint16_t SENSOR_CTRL_AVERAGE_Set(uint16_t reg, uint16_t value) { return (uint16_t)((reg & ~SENSOR_CTRL_AVERAGE_Msk) | (((uint16_t)value << SENSOR_CTRL_AVERAGE_Pos) & SENSOR_CTRL_AVERAGE_Msk)); }
+
+/* Status: Sensor status */
+#define SENSOR_STATUS_OFFSET             0x4UL
+#define SENSOR_STATUS_ADDR               (SENSOR_BASE + SENSOR_STATUS_OFFSET)
+#define SENSOR_STATUS_SIZE               1U
+#define SENSOR_STATUS_RESET              0x80UL
+#define SENSOR_STATUS_READY_Pos          0U
+#define SENSOR_STATUS_READY_Width        1U
+#define SENSOR_STATUS_READY_Msk          (0x1UL << SENSOR_STATUS_READY_Pos)
+static inline uint8_t SENSOR_STATUS_READY_Get(uint8_t reg) { return (uint8_t)((reg & SENSOR_STATUS_READY_Msk) >> SENSOR_STATUS_READY_Pos); }
+#define SENSOR_STATUS_IDLE_Pos           7U
+#define SENSOR_STATUS_IDLE_Width         1U
+#define SENSOR_STATUS_IDLE_Msk           (0x1UL << SENSOR_STATUS_IDLE_Pos)
+static inline uint8_t SENSOR_STATUS_IDLE_Get(uint8_t reg) { return (uint8_t)((reg & SENSOR_STATUS_IDLE_Msk) >> SENSOR_STATUS_IDLE_Pos); }
+
+/* Command */
+#define SENSOR_COMMAND_OFFSET            0x8UL
+#define SENSOR_COMMAND_ADDR              (SENSOR_BASE + SENSOR_COMMAND_OFFSET)
+#define SENSOR_COMMAND_SIZE              4U
+#define SENSOR_COMMAND_RESET             0x00000000UL
+#define SENSOR_COMMAND_CODE_Pos          0U
+#define SENSOR_COMMAND_CODE_Width        16U
+#define SENSOR_COMMAND_CODE_Msk          (0xFFFFUL << SENSOR_COMMAND_CODE_Pos)
+static inline uint32_t SENSOR_COMMAND_CODE_Set(uint32_t reg, uint32_t value) { return (uint32_t)((reg & ~SENSOR_COMMAND_CODE_Msk) | (((uint32_t)value << SENSOR_COMMAND_CODE_Pos) & SENSOR_COMMAND_CODE_Msk)); }
+/* Unlock key */
+#define SENSOR_COMMAND_KEY_0_Pos         16U
+#define SENSOR_COMMAND_KEY_0_Width       4U
+#define SENSOR_COMMAND_KEY_0_Msk         (0xFUL << SENSOR_COMMAND_KEY_0_Pos)
+static inline uint32_t SENSOR_COMMAND_KEY_0_Set(uint32_t reg, uint32_t value) { return (uint32_t)((reg & ~SENSOR_COMMAND_KEY_0_Msk) | (((uint32_t)value << SENSOR_COMMAND_KEY_0_Pos) & SENSOR_COMMAND_KEY_0_Msk)); }
+/* Unlock key */
+#define SENSOR_COMMAND_KEY_1_Pos         24U
+#define SENSOR_COMMAND_KEY_1_Width       4U
+#define SENSOR_COMMAND_KEY_1_Msk         (0xFUL << SENSOR_COMMAND_KEY_1_Pos)
+static inline uint32_t SENSOR_COMMAND_KEY_1_Set(uint32_t reg, uint32_t value) { return (uint32_t)((reg & ~SENSOR_COMMAND_KEY_1_Msk) | (((uint32_t)value << SENSOR_COMMAND_KEY_1_Pos) & SENSOR_COMMAND_KEY_1_Msk)); }
+
+/* Sample: Last measurement */
+#define SENSOR_SAMPLE_OFFSET             0xCUL
+#define SENSOR_SAMPLE_ADDR               (SENSOR_BASE + SENSOR_SAMPLE_OFFSET)
+#define SENSOR_SAMPLE_SIZE               4U
+#define SENSOR_SAMPLE_RESET              0x00000000UL
+/* Raw reading, most significant byte first */
+#define SENSOR_SAMPLE_READING_Pos        0U
+#define SENSOR_SAMPLE_READING_Width      16U
+#define SENSOR_SAMPLE_READING_Msk        (0xFFFFUL << SENSOR_SAMPLE_READING_Pos)
+static inline uint32_t SENSOR_SAMPLE_READING_Get(uint32_t reg) { uint32_t raw = (uint32_t)((reg & SENSOR_SAMPLE_READING_Msk) >> SENSOR_SAMPLE_READING_Pos); return (uint32_t)(((raw & 0xFFUL) << 8U) | ((raw >> 8U) & 0xFFUL)); }
+static inline uint32_t SENSOR_SAMPLE_READING_Set(uint32_t reg, uint32_t value) { uint32_t raw = (uint32_t)(((value & 0xFFUL) << 8U) | ((value >> 8U) & 0xFFUL)); return (uint32_t)((reg & ~SENSOR_SAMPLE_READING_Msk) | ((raw << SENSOR_SAMPLE_READING_Pos) & SENSOR_SAMPLE_READING_Msk)); }
+#define SENSOR_SAMPLE_CHANNEL_Pos        16U
+#define SENSOR_SAMPLE_CHANNEL_Width      4U
+#define SENSOR_SAMPLE_CHANNEL_Msk        (0xFUL << SENSOR_SAMPLE_CHANNEL_Pos)
+static inline uint32_t SENSOR_SAMPLE_CHANNEL_Get(uint32_t reg) { return (uint32_t)((reg & SENSOR_SAMPLE_CHANNEL_Msk) >> SENSOR_SAMPLE_CHANNEL_Pos); }
+static inline uint32_t SENSOR_SAMPLE_CHANNEL_Set(uint32_t reg, uint32_t value) { return (uint32_t)((reg & ~SENSOR_SAMPLE_CHANNEL_Msk) | (((uint32_t)value << SENSOR_SAMPLE_CHANNEL_Pos) & SENSOR_SAMPLE_CHANNEL_Msk)); }
+
+#endif /* SENSOR_DEVICE_H */
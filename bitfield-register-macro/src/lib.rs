@@ -4,52 +4,190 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 
+extern crate proc_macro2;
+use proc_macro2::{Span, TokenStream as Tokens};
+
 extern crate syn;
-use syn::*;
+use syn::{Attribute, Data, DataEnum, DeriveInput, Expr, ExprLit, ExprRange, Field, Fields, Ident, Lit, Meta, RangeLimits, Token, Type};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 
 #[macro_use]
 extern crate quote;
-use quote::Tokens;
 
 #[derive(Debug)]
 enum BitFieldPosition {
-    Single(u8),
-    Range(std::ops::Range<u8>)
+    Single(u32),
+    Range(std::ops::Range<u32>),
+    // pieces of the value, least significant first
+    Split(Vec<std::ops::Range<u32>>)
+}
+
+// up to 8 bits of a field value, contiguous in the register and inside one byte of the value
+#[derive(Debug, PartialEq)]
+struct Segment {
+    value_byte: usize,
+    value_offset: usize,
+    from_bit: usize,
+    bit_length: u8
 }
 
 impl BitFieldPosition {
-    pub fn first_bit(&self) -> usize {
+    pub fn pieces(&self) -> Vec<std::ops::Range<usize>> {
         match self {
-            &BitFieldPosition::Single(x) => x as usize,
-            &BitFieldPosition::Range(ref range) => range.start as usize
+            &BitFieldPosition::Single(x) => vec![x as usize..x as usize + 1],
+            &BitFieldPosition::Range(ref range) => vec![range.start as usize..range.end as usize],
+            &BitFieldPosition::Split(ref pieces) => pieces.iter().map(|piece| piece.start as usize..piece.end as usize).collect()
         }
     }
 
+    pub fn first_bit(&self) -> usize {
+        self.pieces().iter().map(|piece| piece.start).min().unwrap()
+    }
+
     pub fn last_bit(&self) -> usize {
-        match self {
-            &BitFieldPosition::Single(x) => x as usize,
-            &BitFieldPosition::Range(ref range) => (range.end - 1) as usize
-        }
+        self.pieces().iter().map(|piece| piece.end - 1).max().unwrap()
     }
 
     pub fn len(&self) -> usize {
-        match self {
-            &BitFieldPosition::Single(_) => 1,
-            &BitFieldPosition::Range(ref range) => (range.end - range.start) as usize
-        }
+        self.pieces().iter().map(|piece| piece.end - piece.start).sum()
     }
 
     pub fn byte_len(&self) -> usize {
         let len = self.len();
         if len % 8 == 0 { len / 8 } else { len / 8 + 1 }
     }
+
+    pub fn segments(&self) -> Vec<Segment> {
+        let mut segments = vec![];
+        let mut value_bit = 0;
+        for piece in self.pieces() {
+            let mut from_bit = piece.start;
+            while from_bit < piece.end {
+                let bit_length = usize::min(piece.end - from_bit, 8 - value_bit % 8);
+                segments.push(Segment {
+                    value_byte: value_bit / 8,
+                    value_offset: value_bit % 8,
+                    from_bit,
+                    bit_length: bit_length as u8
+                });
+                from_bit += bit_length;
+                value_bit += bit_length;
+            }
+        }
+        return segments;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Access {
+    ReadWrite,
+    ReadOnly,
+    WriteOnly
+}
+
+impl Access {
+    pub fn readable(&self) -> bool {
+        *self != Access::WriteOnly
+    }
+
+    pub fn writable(&self) -> bool {
+        *self != Access::ReadOnly
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChecksumKind {
+    ParityEven,
+    ParityOdd,
+    Crc8,
+    Crc16
+}
+
+// #[checksum(...)] of a register, over and at are bit ranges
+#[derive(Debug, Clone)]
+struct Checksum {
+    kind: ChecksumKind,
+    over: std::ops::Range<usize>,
+    at: std::ops::Range<usize>,
+    // crc16 stored most significant byte first
+    big_endian: bool
+}
+
+impl Checksum {
+    fn overlaps(&self, bitfield: &BitField) -> bool {
+        return bitfield.position.pieces().iter().any(|piece| piece.start < self.at.end && self.at.start < piece.end);
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct RegisterParams {
+    address: Option<u32>,
+    // in bytes, defaults to the smallest size holding every field
+    size: Option<usize>,
+    reset: Option<u64>,
+    // set_x rejects values that don't fit, see emit_setters
+    checked: bool,
+    // byte at a time field access instead of word loads and stores, see emit_word_access
+    bytewise: bool,
+    checksum: Option<Checksum>
 }
 
-#[derive(Debug)]
 struct BitField {
     position: BitFieldPosition,
     ident: Ident,
-    ty: Ty
+    ty: Type,
+    access: Access,
+    docs: Vec<Attribute>,
+    range: Option<ExprRange>,
+    // alias of bits owned by other fields, see check_overlaps
+    overlay: bool,
+    // most significant byte of the value in the lowest register byte
+    big_endian: bool,
+    // (scale, offset) of the physical value raw * scale + offset, see emit_scaled
+    scale: Option<(f64, f64)>
+}
+
+fn doc_attrs(attrs: &Vec<Attribute>) -> Vec<Attribute> {
+    return attrs.iter().filter(|attr| attr.path().is_ident("doc")).cloned().collect();
+}
+
+fn ident(name: &str) -> Ident {
+    return Ident::new(name, Span::call_site());
+}
+
+// doc comment text, one line per doc attribute
+fn doc_string(docs: &Vec<Attribute>) -> Option<String> {
+    let lines: Vec<String> = docs
+        .iter()
+        .filter_map(|attr| match attr.meta {
+            Meta::NameValue(ref nv) => match nv.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(ref value), .. }) => Some(value.value().trim().to_string()),
+                _ => None
+            },
+            _ => None
+        })
+        .collect();
+
+    if lines.is_empty() {
+        return None;
+    }
+    return Some(lines.join("\n"));
+}
+
+fn emit_option<T: quote::ToTokens>(value: Option<T>) -> Tokens {
+    return match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None }
+    };
+}
+
+fn emit_access(access: Access) -> Tokens {
+    return match access {
+        Access::ReadWrite => quote! { ::bitfield_register::Access::ReadWrite },
+        Access::ReadOnly => quote! { ::bitfield_register::Access::ReadOnly },
+        Access::WriteOnly => quote! { ::bitfield_register::Access::WriteOnly }
+    };
 }
 
 fn filled_byte(from:u8, to:u8) -> u8 {
@@ -149,81 +287,743 @@ fn emit_write_single_byte(to:Tokens, from:Tokens, from_bit:usize, bit_length:u8)
     }
 }
 
-fn output_struct(name: &Ident, bitfields: &Vec<BitField>) -> quote::Tokens {
+// bits() and from_bits() of a register of size bytes, byte 0 holds bits 0..8
+fn emit_bits(name: &Ident, size: usize) -> (Tokens, Tokens) {
+    let bits_size: usize = match size {
+        1 => 1,
+        2 => 2,
+        3..=4 => 4,
+        5..=8 => 8,
+        9..=16 => 16,
+        _ => {
+            let methods = quote! {
+                pub fn bits(&self) -> [u8;#size] {
+                    return self.0;
+                }
+                pub fn from_bits(bits: [u8;#size]) -> Self {
+                    return #name(bits);
+                }
+            };
+            return (quote! { [u8;#size] }, methods);
+        }
+    };
+    let bits_ty = ident(&format!("u{}", bits_size * 8));
+
+    let methods = quote! {
+        pub fn bits(&self) -> #bits_ty {
+            let mut bytes = [0u8;#bits_size];
+            bytes[..#size].copy_from_slice(&self.0);
+            return #bits_ty::from_le_bytes(bytes);
+        }
+        pub fn from_bits(bits: #bits_ty) -> Self {
+            let mut data = [0u8;#size];
+            data.copy_from_slice(&bits.to_le_bytes()[..#size]);
+            return #name(data);
+        }
+    };
+    return (quote! { #bits_ty }, methods);
+}
+
+// reads and writes value_array a byte at a time, returns (getter body, write bytes) like emit_word_access
+fn emit_byte_access(position: &BitFieldPosition) -> (Tokens, Tokens) {
+    let value_byte_len = position.byte_len();
+
+    let mut getter_body = quote! {
+        let mut value_array: [u8;#value_byte_len] = [0;#value_byte_len];
+    };
+
+    let mut write_bytes = quote! {};
+
+    let segments = position.segments();
+    for segment in &segments {
+        let i = segment.value_byte;
+        let offset = segment.value_offset;
+        let whole_byte = segments.iter().filter(|other| other.value_byte == i).count() == 1;
+
+        let read_byte = emit_read_single_byte(quote! { self.0 }, segment.from_bit, segment.bit_length);
+        getter_body = if whole_byte {
+            quote! { #getter_body
+                value_array[#i] = #read_byte;
+            }
+        } else {
+            quote! { #getter_body
+                value_array[#i] |= (#read_byte) << #offset;
+            }
+        };
+
+        let value_byte = if offset == 0 { quote! { value_array[#i] } } else { quote! { (value_array[#i] >> #offset) } };
+        let write_byte = emit_write_single_byte(quote!{ self.0 }, value_byte, segment.from_bit, segment.bit_length);
+        write_bytes = quote! { #write_bytes
+            #write_byte;
+        };
+    }
+
+    return (getter_body, write_bytes);
+}
+
+// contiguous fields spanning at most 8 bytes are read as one u8/u16/u32/u64 word:
+// a single load, shift and mask instead of assembling value_array byte by byte
+// returns (getter body, write bytes) working on value_array like the bytewise code
+fn emit_word_access(position: &BitFieldPosition) -> Option<(Tokens, Tokens)> {
+    if let &BitFieldPosition::Split(_) = position {
+        return None;
+    }
+
+    let first_byte = position.first_bit() / 8;
+    let span = position.last_bit() / 8 - first_byte + 1;
+    let (word_ty, word_len) = match span {
+        1 => (quote! { u8 }, 1),
+        2 => (quote! { u16 }, 2),
+        3..=4 => (quote! { u32 }, 4),
+        5..=8 => (quote! { u64 }, 8),
+        _ => return None,
+    };
+
+    let shift = position.first_bit() % 8;
+    let value_byte_len = position.byte_len();
+    let mask = proc_macro2::Literal::u64_unsuffixed(u64::max_value() >> (64 - position.len()));
+    let field_mask = proc_macro2::Literal::u64_unsuffixed(!((u64::max_value() >> (64 - position.len())) << shift) & (u64::max_value() >> (64 - 8 * word_len)));
+
+    let register_bytes: Vec<Tokens> = (0..word_len)
+        .map(|i| if i < span { let byte = first_byte + i; quote! { self.0[#byte] } } else { quote! { 0 } })
+        .collect();
+    let value_bytes: Vec<Tokens> = (0..word_len)
+        .map(|i| if i < value_byte_len { quote! { value_array[#i] } } else { quote! { 0 } })
+        .collect();
+    let value_indices: Vec<usize> = (0..value_byte_len).collect();
+    let end_byte = first_byte + span;
+
+    let getter_body = quote! {
+        let word = (#word_ty::from_le_bytes([#(#register_bytes),*]) >> #shift) & #mask;
+        let word_bytes = word.to_le_bytes();
+        let value_array: [u8;#value_byte_len] = [#(word_bytes[#value_indices]),*];
+    };
+    let write_bytes = quote! {
+        let value = #word_ty::from_le_bytes([#(#value_bytes),*]) & #mask;
+        let word = (#word_ty::from_le_bytes([#(#register_bytes),*]) & #field_mask) | (value << #shift);
+        let word_bytes = word.to_le_bytes();
+        self.0[#first_byte..#end_byte].copy_from_slice(&word_bytes[..#span]);
+    };
+    return Some((getter_body, write_bytes));
+}
+
+// bitwise operators between register values, applied byte by byte
+fn emit_operators(name: &Ident, size: usize) -> Tokens {
+    let bitand = quote! {
+        impl ::bitfield_register::export::ops::BitAnd for #name {
+            type Output = #name;
+            fn bitand(self, other: #name) -> #name {
+                let mut data = self.0;
+                for i in 0..#size {
+                    data[i] &= other.0[i];
+                }
+                return #name(data);
+            }
+        }
+    };
+    let bitor = quote! {
+        impl ::bitfield_register::export::ops::BitOr for #name {
+            type Output = #name;
+            fn bitor(self, other: #name) -> #name {
+                let mut data = self.0;
+                for i in 0..#size {
+                    data[i] |= other.0[i];
+                }
+                return #name(data);
+            }
+        }
+    };
+    let bitxor = quote! {
+        impl ::bitfield_register::export::ops::BitXor for #name {
+            type Output = #name;
+            fn bitxor(self, other: #name) -> #name {
+                let mut data = self.0;
+                for i in 0..#size {
+                    data[i] ^= other.0[i];
+                }
+                return #name(data);
+            }
+        }
+    };
+    let not = quote! {
+        impl ::bitfield_register::export::ops::Not for #name {
+            type Output = #name;
+            fn not(self) -> #name {
+                let mut data = self.0;
+                for i in 0..#size {
+                    data[i] = !data[i];
+                }
+                return #name(data);
+            }
+        }
+    };
+    return quote! { #bitand #bitor #bitxor #not };
+}
+
+// width of primitive unsigned field types, other types are checked on their converted bytes
+fn integer_bits(ty: &Type) -> Option<usize> {
+    let name = match ty {
+        &Type::Path(ref path) if path.qself.is_none() => path_name(&path.path),
+        _ => return None
+    };
+    return match name.as_str() {
+        "u8" => Some(8),
+        "u16" => Some(16),
+        "u32" => Some(32),
+        "u64" => Some(64),
+        "u128" => Some(128),
+        _ => None
+    };
+}
+
+// seal() writes the checksum of the over bits to the at bits, verify_checksum() compares them
+fn emit_checksum(checksum: &Checksum) -> (Tokens, Tokens) {
+    let position = BitFieldPosition::Range(checksum.at.start as u32..checksum.at.end as u32);
+    let (read_at, write_at) = emit_byte_access(&position);
+
+    let (first_bit, last_bit) = (checksum.over.start, checksum.over.end - 1);
+    let (first_byte, end_byte) = (checksum.over.start / 8, checksum.over.end / 8);
+    let value = match checksum.kind {
+        ChecksumKind::ParityEven => quote! { [::bitfield_register::checksum::parity(&self.0, #first_bit, #last_bit) as u8] },
+        ChecksumKind::ParityOdd => quote! { [!::bitfield_register::checksum::parity(&self.0, #first_bit, #last_bit) as u8] },
+        ChecksumKind::Crc8 => quote! { [::bitfield_register::checksum::crc8(&self.0[#first_byte..#end_byte])] },
+        ChecksumKind::Crc16 if checksum.big_endian => quote! { ::bitfield_register::checksum::crc16(&self.0[#first_byte..#end_byte]).to_be_bytes() },
+        ChecksumKind::Crc16 => quote! { ::bitfield_register::checksum::crc16(&self.0[#first_byte..#end_byte]).to_le_bytes() },
+    };
+
+    let seal = quote! {
+        // stores the checksum, setters call it after every write
+        pub fn seal(&mut self) {
+            let value_array = #value;
+            #write_at
+        }
+    };
+    let verify = quote! {
+        // true when the stored checksum matches, e.g. for received values
+        pub fn verify_checksum(&self) -> bool {
+            #read_at
+            return value_array == #value;
+        }
+    };
+    return (seal, verify);
+}
+
+// methods a register struct gets besides the helpers: the accessors of every field and the fixed ones
+fn reserved_names(bitfields: &[BitField]) -> Vec<String> {
+    let mut result: Vec<String> = ["validate", "is_valid", "seal", "verify_checksum", "diff"]
+        .iter().map(|name| name.to_string()).collect();
+    for bitfield in bitfields {
+        let name = bitfield.ident.to_string();
+        result.push(format!("get_{}", name));
+        result.push(format!("set_{}", name));
+        result.push(format!("try_set_{}", name));
+        result.push(format!("set_{}_saturating", name));
+        result.push(format!("set_{}_wrapping", name));
+        result.push(format!("{}_scaled", name));
+        result.push(format!("set_{}_scaled", name));
+    }
+    return result;
+}
+
+// single-bit bool fields get is_x, set_x_bit, clear_x and toggle_x, other integer fields update_x
+// set_x_bit because set_x(bool) is the setter every field has
+// a helper whose name is in reserved, e.g. is_valid of a field named valid, is left out
+// returns (getters, methods changing the register)
+fn emit_helpers(bitfield: &BitField, checked: bool, reserved: &[String]) -> (Tokens, Tokens) {
+    let ty = &bitfield.ty;
+    let (readable, writable) = (bitfield.access.readable(), bitfield.access.writable());
+    let getter = ident(&format!("get_{}", bitfield.ident));
+    let setter = ident(&format!("set_{}", bitfield.ident));
+    let helper = |name: String| if reserved.contains(&name) { None } else { Some(ident(&name)) };
+
+    let is_flag = match ty {
+        &Type::Path(ref path) => path.qself.is_none() && path_name(&path.path) == "bool",
+        _ => false
+    };
+    if is_flag && bitfield.position.len() == 1 {
+        let wrapping_setter = if checked { ident(&format!("set_{}_wrapping", bitfield.ident)) } else { setter };
+
+        let mut getters = quote! {};
+        if let (true, Some(is)) = (readable, helper(format!("is_{}", bitfield.ident))) {
+            getters = quote! {
+                pub fn #is(&self) -> bool {
+                    return self.#getter();
+                }
+            };
+        }
+        let mut helpers = quote! {};
+        if let (true, Some(set_bit)) = (writable, helper(format!("set_{}_bit", bitfield.ident))) {
+            helpers = quote! { #helpers
+                pub fn #set_bit(&mut self) {
+                    self.#wrapping_setter(true);
+                }
+            };
+        }
+        if let (true, Some(clear)) = (writable, helper(format!("clear_{}", bitfield.ident))) {
+            helpers = quote! { #helpers
+                pub fn #clear(&mut self) {
+                    self.#wrapping_setter(false);
+                }
+            };
+        }
+        if let (true, Some(toggle)) = (readable && writable, helper(format!("toggle_{}", bitfield.ident))) {
+            helpers = quote! { #helpers
+                pub fn #toggle(&mut self) {
+                    let value = self.#getter();
+                    self.#wrapping_setter(!value);
+                }
+            };
+        }
+        return (getters, helpers);
+    }
+
+    if integer_bits(ty).is_none() || bitfield.position.len() < 2 || !(readable && writable) {
+        return (quote! {}, quote! {});
+    }
+
+    let update = match helper(format!("update_{}", bitfield.ident)) {
+        Some(update) => update,
+        None => return (quote! {}, quote! {}),
+    };
+    // same result as set_x: () or, with #[register(checked)], Result
+    let helpers = if checked {
+        quote! {
+            pub fn #update<F: FnOnce(#ty) -> #ty>(&mut self, update: F) -> Result<(), ::bitfield_register::FieldError> {
+                let value = self.#getter();
+                return self.#setter(update(value));
+            }
+        }
+    } else {
+        quote! {
+            pub fn #update<F: FnOnce(#ty) -> #ty>(&mut self, update: F) {
+                let value = self.#getter();
+                self.#setter(update(value));
+            }
+        }
+    };
+    return (quote! {}, helpers);
+}
+
+// x_scaled and set_x_scaled for fields with a scale or offset, physical value = raw * scale + offset
+// set_x_scaled rounds to the nearest raw value and fails with OutOfRange when it doesn't fit the field
+fn emit_scaled(bitfield: &BitField) -> Option<(Tokens, Tokens)> {
+    let (scale_value, offset_value) = bitfield.scale?;
+    let scale = proc_macro2::Literal::f32_suffixed(scale_value as f32);
+    let offset = proc_macro2::Literal::f32_suffixed(offset_value as f32);
+    let ty = &bitfield.ty;
+    let field_name = bitfield.ident.to_string();
+    let max = proc_macro2::Literal::f32_suffixed((2f64.powi(bitfield.position.len() as i32) - 1.0) as f32);
+
+    let getter = ident(&format!("get_{}", bitfield.ident));
+    let try_setter = ident(&format!("try_set_{}", bitfield.ident));
+    let scaled_getter = ident(&format!("{}_scaled", bitfield.ident));
+    let scaled_setter = ident(&format!("set_{}_scaled", bitfield.ident));
+
+    let getter_doc = format!(" Physical value, raw * {} + {}.", scale_value, offset_value);
+    let setter_doc = format!(" Stores the raw value nearest to (value - {}) / {}.", offset_value, scale_value);
+
+    let scaled_getter_fn = quote! {
+        #[doc = #getter_doc]
+        pub fn #scaled_getter(&self) -> f32 {
+            return self.#getter() as f32 * #scale + #offset;
+        }
+    };
+    let scaled_setter_fn = quote! {
+        #[doc = #setter_doc]
+        pub fn #scaled_setter(&mut self, value: f32) -> Result<(), ::bitfield_register::FieldError> {
+            let raw = (value - #offset) / #scale;
+            let raw = if raw >= 0.0 { raw + 0.5 } else { raw - 0.5 };
+            // also rejects NaN
+            if !(raw > -1.0 && raw < #max + 1.0) {
+                return Err(::bitfield_register::FieldError::OutOfRange { field: #field_name });
+            }
+            return self.#try_setter(raw as #ty);
+        }
+    };
+    return Some((scaled_getter_fn, scaled_setter_fn));
+}
+
+// set_x stores the low bits of the value, try_set_x rejects values that don't fit the field or its range,
+// set_x_saturating clamps integers to the field maximum
+// with #[register(checked)] set_x is the checked form and set_x_wrapping the storing one
+// write_bytes writes value_array to the register bytes
+fn emit_setters(bitfield: &BitField, checked: bool, write_bytes: Tokens) -> Tokens {
+    let docs = &bitfield.docs;
+    let ty = &bitfield.ty;
+    let field_name = bitfield.ident.to_string();
+    let len = bitfield.position.len();
+    let value_byte_len = bitfield.position.byte_len();
+
+    let setter = ident(&format!("set_{}", bitfield.ident));
+    let try_setter = ident(&format!("try_set_{}", bitfield.ident));
+    let saturating_setter = ident(&format!("set_{}_saturating", bitfield.ident));
+    let wrapping_setter = if checked { ident(&format!("set_{}_wrapping", bitfield.ident)) } else { setter.clone() };
+
+    let convert = quote! {
+        let value_array: [u8;#value_byte_len] = ::bitfield_register::IntoBitfield::into_bitfield(value);
+    };
+    let overflow = quote! {
+        return Err(::bitfield_register::FieldError::Overflow { field: #field_name });
+    };
+
+    let range_check = match bitfield.range {
+        Some(ref range) => quote! {
+            if !(#range).contains(&value) {
+                return Err(::bitfield_register::FieldError::OutOfRange { field: #field_name });
+            }
+        },
+        None => quote! {}
+    };
+
+    let max = match integer_bits(ty) {
+        Some(bits) if bits > len => Some(proc_macro2::Literal::u128_unsuffixed((1u128 << len) - 1)),
+        _ => None
+    };
+
+    // integers are compared with the field maximum, other types by the bits above the field in the last byte
+    let checked_write = match (integer_bits(ty), max.clone()) {
+        (Some(_), Some(max)) => quote! {
+            if value > #max {
+                #overflow
+            }
+            #convert
+            #write_bytes
+        },
+        (Some(_), None) => quote! {
+            #convert
+            #write_bytes
+        },
+        (None, _) if len % 8 == 0 => quote! {
+            #convert
+            #write_bytes
+        },
+        (None, _) => {
+            let last = value_byte_len - 1;
+            let high_bits = !filled_byte(0, (len % 8) as u8);
+            quote! {
+                #convert
+                if value_array[#last] & #high_bits != 0 {
+                    #overflow
+                }
+                #write_bytes
+            }
+        }
+    };
+
+    let wrapping_doc = format!(" Stores the low {} bits of the value, see `{}` for the checked form.", len, try_setter);
+    let mut result = quote! {
+        #(#docs)*
+        #[doc = #wrapping_doc]
+        pub fn #wrapping_setter(&mut self, value: #ty) -> () {
+            #convert
+            #write_bytes
+        }
+
+        #(#docs)*
+        pub fn #try_setter(&mut self, value: #ty) -> Result<(), ::bitfield_register::FieldError> {
+            #range_check
+            #checked_write
+            return Ok(());
+        }
+    };
+
+    if checked {
+        result = quote! { #result
+            #(#docs)*
+            pub fn #setter(&mut self, value: #ty) -> Result<(), ::bitfield_register::FieldError> {
+                return self.#try_setter(value);
+            }
+        };
+    }
+
+    if let Some(_) = integer_bits(ty) {
+        let clamp = match max {
+            Some(max) => quote! { let value = if value > #max { #max } else { value }; },
+            None => quote! {}
+        };
+        result = quote! { #result
+            #(#docs)*
+            pub fn #saturating_setter(&mut self, value: #ty) -> () {
+                #clamp
+                self.#wrapping_setter(value);
+            }
+        };
+    }
+
+    return result;
+}
+
+fn output_struct(name: &Ident, params: &RegisterParams, docs: &Vec<Attribute>, bitfields: &Vec<BitField>) -> Tokens {
+    let checksum_last_bit = params.checksum.as_ref().map(|checksum| usize::max(checksum.over.end, checksum.at.end) - 1);
     let last_bit = bitfields
         .iter()
         .map(|x| x.position.last_bit())
+        .chain(checksum_last_bit)
         .max()
         .unwrap();
 
-    let base_size: usize = last_bit / 8 + 1;
+    if let Some(ref checksum) = params.checksum {
+        if bitfields.iter().any(|bitfield| checksum.overlaps(bitfield)) {
+            panic!("fields overlap the checksum bits");
+        }
+    }
+
+    let base_size: usize = match params.size {
+        Some(size) => {
+            if last_bit / 8 + 1 > size {
+                panic!("bitfield does not fit register size");
+            }
+            size
+        },
+        None => last_bit / 8 + 1
+    };
+
+    let reset = params.reset.unwrap_or(0);
+    if base_size < 8 && reset >> (8 * base_size) != 0 {
+        panic!("reset value does not fit register size");
+    }
+    let reset_bytes: Vec<u8> = (0..base_size)
+        .map(|i| if i < 8 { (reset >> (8 * i)) as u8 } else { 0 })
+        .collect();
 
     let mut impl_body = quote! {};
+    // getters only, for the read-only view
+    let mut getters = quote! {};
+    let mut masks = quote! {};
+    // Option<FieldError> expressions of fields with a range, for validate()
+    let mut range_checks: Vec<Tokens> = vec![];
+    let mut field_descriptions = quote! {};
+    let reserved = reserved_names(bitfields);
 
     for bitfield in bitfields {
         // println!("iter field {} @{:?}", bitfield.ident, bitfield.position);
 
-        let getter_str = format!("get_{}", bitfield.ident.as_ref());
-        let getter: Ident = From::from(getter_str.as_str());
-
-        let setter_str = format!("set_{}", bitfield.ident.as_ref());
-        let setter: Ident = From::from(setter_str.as_str());
+        let getter = ident(&format!("get_{}", bitfield.ident));
 
         let ty = &bitfield.ty;
 
         let first_bit = bitfield.position.first_bit();
         let last_bit = bitfield.position.last_bit();
-        let value_byte_len = bitfield.position.byte_len();
 
-        let mut getter_body = quote! {
-            let mut value_array: [u8;#value_byte_len] = [0;#value_byte_len];
-        };
+        let word_access = if params.bytewise { None } else { emit_word_access(&bitfield.position) };
+        let (mut getter_body, mut write_bytes) = word_access.unwrap_or_else(|| emit_byte_access(&bitfield.position));
+
+        // value_array is little endian, big endian fields swap it after reading and before writing
+        if bitfield.big_endian {
+            getter_body = quote! { #getter_body
+                let mut value_array = value_array;
+                value_array.reverse();
+            };
+            write_bytes = quote! {
+                let mut value_array = value_array;
+                value_array.reverse();
+                #write_bytes
+            };
+        }
+
+        // println!("getter body {}", getter_body);
+        // println!("write bytes {}", write_bytes);
 
-        let mut setter_body = quote! {
-            let value_array: [u8;#value_byte_len] = ::bitfield_register::IntoBitfield::into_bitfield(value);
+        let docs = &bitfield.docs;
+
+        let pieces = bitfield.position.pieces();
+        let mask_name = ident(&format!("{}_MASK", bitfield.ident.to_string().to_uppercase()));
+        let mask_bytes: Vec<u8> = (0..base_size)
+            .map(|byte| (0..8).filter(|bit| pieces.iter().any(|piece| piece.contains(&(byte * 8 + bit)))).fold(0u8, |mask, bit| mask | 1 << bit))
+            .collect();
+        masks = quote! { #masks
+            pub const #mask_name: #name = #name([#(#mask_bytes),*]);
         };
 
-        for i in 0..value_byte_len {
-            let from_bit_i = first_bit + 8 * i;
-            let to_bit_i = usize::min(from_bit_i + 8, last_bit + 1);
-            let bit_length = (to_bit_i -  from_bit_i) as u8;
+        let range_firsts: Vec<usize> = pieces.iter().map(|piece| piece.start).collect();
+        let range_lasts: Vec<usize> = pieces.iter().map(|piece| piece.end - 1).collect();
+        let overlay = bitfield.overlay;
+        let big_endian = bitfield.big_endian;
+        let field_name = bitfield.ident.to_string();
+        let field_doc = emit_option(doc_string(docs));
+        let access = emit_access(bitfield.access);
+        field_descriptions = quote! { #field_descriptions
+            ::bitfield_register::FieldDescription {
+                name: #field_name,
+                description: #field_doc,
+                first_bit: #first_bit,
+                last_bit: #last_bit,
+                ranges: &[#((#range_firsts, #range_lasts)),*],
+                access: #access,
+                alias: #overlay,
+                big_endian: #big_endian,
+            },
+        };
 
-            let read_byte = emit_read_single_byte(quote! { self.0 }, from_bit_i, bit_length);
-            getter_body = quote! { #getter_body
-                value_array[#i] = #read_byte;
+        if bitfield.access.readable() {
+            let getter_fn = quote! {
+                #(#docs)*
+                pub fn #getter(&self) -> #ty {
+                    #getter_body
+                    return ::bitfield_register::FromBitfield::from_bitfield(value_array);
+                }
             };
+            impl_body = quote! { #impl_body #getter_fn };
+            getters = quote! { #getters #getter_fn };
+        }
 
-            let write_byte = emit_write_single_byte(quote!{ self.0 }, quote! { value_array[#i] }, from_bit_i, bit_length);
-            setter_body = quote! { #setter_body
-                #write_byte;
-            };
+        // setters keep the checksum up to date
+        let write_bytes = if params.checksum.is_some() {
+            quote! { #write_bytes self.seal(); }
+        } else {
+            write_bytes
+        };
+
+        if bitfield.access.writable() {
+            let setters = emit_setters(bitfield, params.checked, write_bytes);
+            impl_body = quote! { #impl_body #setters };
         }
 
-        // println!("getter body {}", getter_body);
-        // println!("setter body {}", setter_body);
+        let (flag_getters, helpers) = emit_helpers(bitfield, params.checked, &reserved);
+        impl_body = quote! { #impl_body #flag_getters #helpers };
+        getters = quote! { #getters #flag_getters };
 
-        impl_body = quote! {
-            #impl_body
+        if let Some((scale_getter, scale_setter)) = emit_scaled(bitfield) {
+            if bitfield.access.readable() {
+                impl_body = quote! { #impl_body #scale_getter };
+                getters = quote! { #getters #scale_getter };
+            }
+            if bitfield.access.writable() {
+                impl_body = quote! { #impl_body #scale_setter };
+            }
+        }
+
+        if let Some(ref range) = bitfield.range {
+            range_checks.push(quote! {
+                {
+                    #getter_body
+                    let value: #ty = ::bitfield_register::FromBitfield::from_bitfield(value_array);
+                    if (#range).contains(&value) {
+                        None
+                    } else {
+                        Some(::bitfield_register::FieldError::OutOfRange { field: #field_name })
+                    }
+                }
+            });
+        }
+    };
+
+    let range_check_count = range_checks.len();
+    let validate = quote! {
+        pub fn validate(&self) -> impl Iterator<Item = ::bitfield_register::FieldError> {
+            let errors: [Option<::bitfield_register::FieldError>;#range_check_count] = [#(#range_checks),*];
+            return IntoIterator::into_iter(errors).flatten();
+        }
+        pub fn is_valid(&self) -> bool {
+            return self.validate().next().is_none();
+        }
+    };
+    impl_body = quote! { #impl_body #validate };
+    getters = quote! { #getters #validate };
 
-            pub fn #getter(&self) -> #ty {
-                #getter_body
-                return ::bitfield_register::FromBitfield::from_bitfield(value_array);
+    if let Some(ref checksum) = params.checksum {
+        let (seal, verify) = emit_checksum(checksum);
+        impl_body = quote! { #impl_body #seal #verify };
+        getters = quote! { #getters #verify };
+    }
+
+    let address_impl = match params.address {
+        Some(address) => quote! {
+            impl ::bitfield_register::AddressedRegister for #name {
+                const ADDRESS: u32 = #address;
             }
+        },
+        None => quote! {}
+    };
+
+    let register_name = name.to_string();
+    let register_doc = emit_option(doc_string(docs));
+    let register_address = emit_option(params.address);
+    let register_reset = reset_bytes.clone();
 
-            pub fn #setter(&mut self, value: #ty) -> () {
-                #setter_body
+    let (bits_ty, bits_methods) = emit_bits(name, base_size);
+    let operators = emit_operators(name, base_size);
+
+    // views over base_size bytes of a borrowed buffer, self.0 is a slice there, so getters and setters are shared
+    let ref_name = ident(&format!("{}Ref", name));
+    let mut_name = ident(&format!("{}Mut", name));
+    let view_methods = quote! {
+        pub fn as_bytes(&self) -> &[u8] {
+            return &self.0[..];
+        }
+        pub fn to_register(&self) -> #name {
+            let mut data = [0u8;#base_size];
+            data.copy_from_slice(&self.0[..]);
+            return #name(data);
+        }
+    };
+    let length_check = quote! {
+        if offset > buffer.len() || buffer.len() - offset < #base_size {
+            return Err(::bitfield_register::LengthError { expected: offset.saturating_add(#base_size), actual: buffer.len() });
+        }
+    };
+    let views = quote! {
+        #(#docs)*
+        pub struct #ref_name<'a> (&'a [u8]);
+        impl<'a> #ref_name<'a> {
+            // register at buffer[offset..offset + REGISTER_SIZE]
+            pub fn new(buffer: &'a [u8], offset: usize) -> Result<Self, ::bitfield_register::LengthError> {
+                #length_check
+                return Ok(#ref_name(&buffer[offset..offset + #base_size]));
             }
-        };
+            #view_methods
+            #getters
+        }
+        #(#docs)*
+        pub struct #mut_name<'a> (&'a mut [u8]);
+        impl<'a> #mut_name<'a> {
+            // register at buffer[offset..offset + REGISTER_SIZE]
+            pub fn new(buffer: &'a mut [u8], offset: usize) -> Result<Self, ::bitfield_register::LengthError> {
+                #length_check
+                return Ok(#mut_name(&mut buffer[offset..offset + #base_size]));
+            }
+            #view_methods
+            // copies the whole register into the buffer
+            pub fn write(&mut self, register: &#name) {
+                self.0.copy_from_slice(&register.0);
+            }
+            #impl_body
+        }
     };
 
     return quote! {
+        #(#docs)*
         pub struct #name ([u8;#base_size]);
         impl ::bitfield_register::BitfieldRegister for #name {
             type Data = [u8;#base_size];
+            type IntoBits = #bits_ty;
             const REGISTER_SIZE: usize = #base_size;
             fn data(&self) -> &[u8;#base_size] {
                 &self.0
             }
+            fn data_mut(&mut self) -> &mut [u8;#base_size] {
+                return &mut self.0;
+            }
+            fn into_inner(self) -> [u8;#base_size] {
+                return self.0;
+            }
+            fn as_bytes(&self) -> &[u8] {
+                return &self.0;
+            }
+            fn try_from_slice(slice: &[u8]) -> Result<Self, ::bitfield_register::LengthError> {
+                if slice.len() != #base_size {
+                    return Err(::bitfield_register::LengthError { expected: #base_size, actual: slice.len() });
+                }
+                let mut data = [0u8;#base_size];
+                data.copy_from_slice(slice);
+                return Ok(#name(data));
+            }
+            fn bits(&self) -> #bits_ty {
+                return #name::bits(self);
+            }
+            fn from_bits(bits: #bits_ty) -> Self {
+                return #name::from_bits(bits);
+            }
         }
         impl From<[u8;#base_size]> for #name {
             fn from(buffer: [u8;#base_size]) -> Self {
@@ -232,69 +1032,405 @@ fn output_struct(name: &Ident, bitfields: &Vec<BitField>) -> quote::Tokens {
         }
         impl Default for #name {
             fn default() -> Self {
-                return #name ([0;#base_size]);
+                return #name ([#(#reset_bytes),*]);
             }
         }
         impl #name {
+            #masks
+            #bits_methods
+            // fields that differ from other, with raw old and new values
+            pub fn diff<'a>(&'a self, other: &'a #name) -> ::bitfield_register::FieldDiff<'a> {
+                return ::bitfield_register::FieldDiff::new(<#name as ::bitfield_register::DescribedRegister>::DESCRIPTION.fields, &self.0, &other.0);
+            }
             #impl_body
         }
+        #operators
         impl Clone for #name {
             fn clone(&self) -> Self {
                 return #name (self.0.clone());
             }
         }
+        #views
+        #address_impl
+        impl ::bitfield_register::DescribedRegister for #name {
+            const DESCRIPTION: ::bitfield_register::RegisterDescription = ::bitfield_register::RegisterDescription {
+                name: #register_name,
+                description: #register_doc,
+                address: #register_address,
+                size: #base_size,
+                reset: &[#(#register_reset),*],
+                fields: &[#field_descriptions],
+            };
+        }
     }
 }
 
-fn get_register_item_params(field : &Field) -> Result<&Vec<NestedMetaItem>, &str> {
-    let mut result : Result<&Vec<NestedMetaItem>, &str> = Err(
-        "bitfield() meta not found. select bit parameters (use #[bitfield(at=x or from=x to=y)])"
-    );
+// #[register] on an enum: every variant is a register struct <Enum><Variant> sharing the mode field,
+// the enum holds one of them and is decoded and encoded by the value of the mode field
+fn output_enum(name: &Ident, params: &RegisterParams, docs: &Vec<Attribute>, attrs: &Vec<Attribute>, data: &DataEnum) -> Tokens {
+    let mode_position = parse_mode(attrs).unwrap();
+    let mode_len = mode_position.len();
+    let mode_first_bit = mode_position.first_bit();
+    if mode_position.last_bit() >= 64 {
+        panic!("mode field should be within the first 64 bits");
+    }
+    let mode_ty: Type = syn::parse_str(match mode_len {
+        0..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        _ => "u64",
+    }).unwrap();
+    let mode_mask = (u64::max_value() >> (64 - mode_len)) << mode_first_bit;
+
+    let mut variants: Vec<(&syn::Variant, u64, Vec<BitField>)> = vec![];
+    for variant in &data.variants {
+        let value = match variant.discriminant {
+            Some((_, ref expr)) => parse_int(expr).expect("mode value should be an integer"),
+            None => panic!("set the mode value of every variant (use Variant = x)"),
+        };
+        if mode_len < 64 && value >> mode_len != 0 {
+            panic!("mode value does not fit the mode field");
+        }
+        if variants.iter().any(|&(_, other, _)| other == value) {
+            panic!("mode values should be unique");
+        }
 
-    for attr in &field.attrs {
-        if let MetaItem::List(ref attr_ident, ref attr_nest) = attr.value {
-            if attr_ident != "bitfield" {
+        let mut bitfields = vec![BitField {
+            position: parse_mode(attrs).unwrap(),
+            ident: ident("mode"),
+            ty: mode_ty.clone(),
+            access: Access::ReadOnly,
+            docs: vec![],
+            range: None,
+            overlay: false,
+            big_endian: false,
+            scale: None
+        }];
+        match variant.fields {
+            Fields::Named(ref fields) => {
+                for field in &fields.named {
+                    let bitfield = parse_bitfield(field).unwrap();
+                    check_variant_field(&bitfield).unwrap();
+                    bitfields.push(bitfield);
+                }
+            },
+            Fields::Unit => {},
+            Fields::Unnamed(_) => panic!("tuple variants not supported"),
+        }
+        check_overlaps(&bitfields).unwrap();
+        variants.push((variant, value, bitfields));
+    }
+    if variants.is_empty() {
+        panic!("enum register should have variants");
+    }
+
+    // every variant has the size of the largest one
+    let last_bit = variants.iter().flat_map(|&(_, _, ref bitfields)| bitfields.iter().map(|x| x.position.last_bit())).max().unwrap();
+    let size = params.size.unwrap_or(last_bit / 8 + 1);
+
+    let mut structs = quote! {};
+    let mut enum_variants = quote! {};
+    let mut decode_arms = quote! {};
+    let mut encode_arms = quote! {};
+    let mut mode_arms = quote! {};
+    let mut conversions = quote! {};
+    for &(variant, value, ref bitfields) in &variants {
+        let variant_ident = &variant.ident;
+        let struct_name = ident(&format!("{}{}", name, variant_ident));
+        let variant_docs = doc_attrs(&variant.attrs);
+        let variant_params = RegisterParams {
+            size: Some(size),
+            reset: Some(params.reset.unwrap_or(0) & !mode_mask | value << mode_first_bit),
+            ..params.clone()
+        };
+        let register = output_struct(&struct_name, &variant_params, &variant_docs, bitfields);
+        let value = proc_macro2::Literal::u64_unsuffixed(value);
+
+        structs = quote! { #structs #register };
+        enum_variants = quote! { #enum_variants
+            #(#variant_docs)*
+            #variant_ident(#struct_name),
+        };
+        decode_arms = quote! { #decode_arms
+            #value => Ok(#name::#variant_ident(#struct_name::from(data))),
+        };
+        encode_arms = quote! { #encode_arms
+            &#name::#variant_ident(ref register) => (register.0, #struct_name::default().0, #struct_name::MODE_MASK.0),
+        };
+        mode_arms = quote! { #mode_arms
+            &#name::#variant_ident(_) => #value,
+        };
+        conversions = quote! { #conversions
+            impl From<#struct_name> for #name {
+                fn from(register: #struct_name) -> Self {
+                    return #name::#variant_ident(register);
+                }
+            }
+        };
+    }
+    let first_struct = ident(&format!("{}{}", name, variants[0].0.ident));
+
+    let io = match params.address {
+        Some(address) => quote! {
+            impl #name {
+                pub const ADDRESS: u32 = #address;
+
+                // Err(FieldError) inside Ok when the device holds an unknown mode
+                pub fn read<I: ::bitfield_register::RegisterInterface>(interface: &mut I) -> Result<Result<Self, ::bitfield_register::FieldError>, I::Error> {
+                    let mut data = [0u8;#size];
+                    interface.read_bytes(#address, &mut data)?;
+                    return Ok(#name::decode(data));
+                }
+
+                pub fn write<I: ::bitfield_register::RegisterInterface>(&self, interface: &mut I) -> Result<(), I::Error> {
+                    return interface.write_bytes(#address, &self.encode());
+                }
+            }
+        },
+        None => quote! {},
+    };
+
+    return quote! {
+        #structs
+
+        #(#docs)*
+        #[derive(Clone)]
+        pub enum #name {
+            #enum_variants
+        }
+        impl #name {
+            // the variant selected by the mode field, FieldError::OutOfRange for unknown modes
+            pub fn decode(data: [u8;#size]) -> Result<Self, ::bitfield_register::FieldError> {
+                return match #first_struct::from(data).get_mode() {
+                    #decode_arms
+                    _ => Err(::bitfield_register::FieldError::OutOfRange { field: "mode" }),
+                };
+            }
+            // register bytes with the mode field set to the mode of the variant
+            pub fn encode(&self) -> [u8;#size] {
+                let (mut data, mode, mask) = match self {
+                    #encode_arms
+                };
+                for i in 0..#size {
+                    data[i] = data[i] & !mask[i] | mode[i] & mask[i];
+                }
+                return data;
+            }
+            pub fn mode(&self) -> #mode_ty {
+                return match self {
+                    #mode_arms
+                };
+            }
+        }
+        #conversions
+        #io
+    };
+}
+
+type MetaList = Punctuated<Meta, Token![,]>;
+
+fn path_name(path: &syn::Path) -> String {
+    return path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+}
+
+fn parse_int(expr: &Expr) -> Option<u64> {
+    return match expr {
+        &Expr::Lit(ExprLit { lit: Lit::Int(ref value), .. }) => value.base10_parse::<u64>().ok(),
+        _ => None
+    };
+}
+
+// float or integer literal, optionally negated
+fn parse_float(expr: &Expr) -> Option<f64> {
+    return match expr {
+        &Expr::Lit(ExprLit { lit: Lit::Float(ref value), .. }) => value.base10_parse::<f64>().ok(),
+        &Expr::Lit(ExprLit { lit: Lit::Int(ref value), .. }) => value.base10_parse::<f64>().ok(),
+        &Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), ref expr, .. }) => parse_float(expr).map(|value| -value),
+        _ => None
+    };
+}
+
+fn parse_register_params(args: &str) -> Result<RegisterParams, &'static str> {
+    let mut params: RegisterParams = Default::default();
+
+    let metas = match args.parse::<Tokens>().ok().and_then(|tokens| MetaList::parse_terminated.parse2(tokens).ok()) {
+        Some(metas) => metas,
+        None => return Err("unsupported register params (use #[register(address = x)])"),
+    };
+
+    for meta in &metas {
+        let (name, value) = match meta {
+            &Meta::NameValue(ref nv) => match parse_int(&nv.value) {
+                Some(value) => (path_name(&nv.path), value),
+                None => return Err("register params should be integers"),
+            },
+            &Meta::Path(ref path) if path.is_ident("checked") => {
+                params.checked = true;
+                continue;
+            }
+            &Meta::Path(ref path) if path.is_ident("bytewise") => {
+                params.bytewise = true;
                 continue;
             }
+            _ => return Err("unsupported register param (use 'address', 'size', 'reset', 'checked' or 'bytewise')"),
+        };
 
-            if result.is_ok() {
-                return Err("Duplicate register metadata found");
+        match name.as_str() {
+            "address" => {
+                if value > u32::max_value() as u64 {
+                    return Err("register address does not fit u32");
+                }
+                params.address = Some(value as u32);
             }
-            result = Ok(&attr_nest);
+            "size" => {
+                if value == 0 {
+                    return Err("register size should be positive");
+                }
+                params.size = Some(value as usize);
+            }
+            "reset" => params.reset = Some(value),
+            _ => return Err("unsupported register param (use 'address', 'size', 'reset', 'checked' or 'bytewise')"),
+        }
+    }
+
+    return Ok(params);
+}
+
+fn get_register_item_params(field : &Field) -> Result<MetaList, &'static str> {
+    let mut result : Result<MetaList, &'static str> = Err(
+        "bitfield() meta not found. select bit parameters (use #[bitfield(at=x or from=x to=y)])"
+    );
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("bitfield") {
+            continue;
+        }
+
+        if result.is_ok() {
+            return Err("Duplicate register metadata found");
         }
+        result = attr.parse_args_with(MetaList::parse_terminated).map_err(|_| "invalid bitfield params");
     }
 
     return result;
 }
 
-fn parse_register_item_params(params:&Vec<NestedMetaItem>) -> Result<(Option<u8>, Option<u8>, Option<u8>), &str> {
-    let mut from: Option<u8> = None;
-    let mut to: Option<u8> = None;
-    let mut at: Option<u8> = None;
+fn parse_access(value: &str) -> Result<Access, &'static str> {
+    return match value {
+        "read-write" => Ok(Access::ReadWrite),
+        "read-only" => Ok(Access::ReadOnly),
+        "write-only" => Ok(Access::WriteOnly),
+        _ => Err("unsupported access (use 'read-write', 'read-only' or 'write-only')"),
+    };
+}
+
+// bit number of at, from or to, below u32::MAX since ranges are stored with an exclusive end
+fn parse_bit(value: &Expr) -> Result<u32, &'static str> {
+    return match parse_int(value) {
+        Some(bit) if bit < u32::max_value() as u64 => Ok(bit as u32),
+        Some(_) => Err("bit position does not fit u32"),
+        None => Err("bit position should be an integer"),
+    };
+}
+
+// a piece of a split field: a bit or an inclusive/exclusive range, e.g. 3, 0..=3, 8..12
+fn parse_piece(value: &Expr) -> Result<std::ops::Range<u32>, &'static str> {
+    if let &Expr::Range(ExprRange { start: Some(ref start), end: Some(ref end), ref limits, .. }) = value {
+        let start = parse_bit(start)?;
+        let end = match limits {
+            &RangeLimits::Closed(_) => parse_bit(end)? + 1,
+            &RangeLimits::HalfOpen(_) => parse_bit(end)?,
+        };
+        if start >= end {
+            return Err("bits piece should not be empty");
+        }
+        return Ok(start..end);
+    }
+
+    let bit = parse_bit(value)?;
+    return Ok(bit..bit + 1);
+}
+
+fn parse_pieces(values: &Punctuated<Expr, Token![,]>) -> Result<Vec<std::ops::Range<u32>>, &'static str> {
+    let pieces = values.iter().map(parse_piece).collect::<Result<Vec<_>, _>>()?;
+    if pieces.is_empty() {
+        return Err("bits should list at least one piece");
+    }
+    for (i, piece) in pieces.iter().enumerate() {
+        if pieces[..i].iter().any(|other| other.start < piece.end && piece.start < other.end) {
+            return Err("bits pieces should not overlap");
+        }
+    }
+    return Ok(pieces);
+}
+
+#[derive(Default)]
+struct BitFieldParams {
+    from: Option<u32>,
+    to: Option<u32>,
+    at: Option<u32>,
+    // pieces of a split field, least significant first
+    bits: Option<Vec<std::ops::Range<u32>>>,
+    access: Option<Access>,
+    // allowed values, e.g. 1..=12
+    range: Option<ExprRange>,
+    overlay: bool,
+    big_endian: bool,
+    scale: Option<f64>,
+    offset: Option<f64>
+}
+
+fn parse_register_item_params(params: &MetaList) -> Result<BitFieldParams, &'static str> {
+    let mut result: BitFieldParams = Default::default();
 
     for param in params {
-        match param.clone() {
-            NestedMetaItem::MetaItem(MetaItem::NameValue(nv_ident, Lit::Int(nv_value, _))) => {
-                match nv_ident.as_ref() {
-                    "at" => (at = Some(nv_value as u8)),
-                    "from" => (from = Some(nv_value as u8)),
-                    "to" => (to = Some(nv_value as u8)),
-                    _ => return Err("unsupported param name (use 'at' or 'from'/'to')"),
-                }
+        let nv = match param {
+            &Meta::NameValue(ref nv) => nv,
+            &Meta::Path(ref path) if path_name(path) == "overlay" => {
+                result.overlay = true;
+                continue;
+            }
+            _ => return Err("unsupported param (use name = value or 'overlay')"),
+        };
+
+        match (path_name(&nv.path).as_str(), &nv.value) {
+            ("at", value) => result.at = Some(parse_bit(value)?),
+            ("from", value) => result.from = Some(parse_bit(value)?),
+            ("to", value) => result.to = Some(parse_bit(value)?),
+            ("bits", &Expr::Array(ref array)) => result.bits = Some(parse_pieces(&array.elems)?),
+            ("bits", _) => return Err("bits should be a list of bit ranges, e.g. bits = [0..=3, 8..=11]"),
+            ("access", &Expr::Lit(ExprLit { lit: Lit::Str(ref value), .. })) => {
+                result.access = Some(parse_access(&value.value())?);
+            }
+            ("range", &Expr::Range(ref range)) => result.range = Some(range.clone()),
+            ("range", _) => return Err("range should be a range expression, e.g. range = 1..=12"),
+            ("endian", &Expr::Lit(ExprLit { lit: Lit::Str(ref value), .. })) => {
+                result.big_endian = match value.value().as_str() {
+                    "little" => false,
+                    "big" => true,
+                    _ => return Err("unsupported endian (use 'little' or 'big')"),
+                };
             }
-            _ => {}
+            ("scale", value) => result.scale = Some(parse_float(value).ok_or("scale should be a number")?),
+            ("offset", value) => result.offset = Some(parse_float(value).ok_or("offset should be a number")?),
+            _ => return Err("unsupported param name (use 'at', 'from'/'to' or 'bits', 'access', 'range', 'endian', 'scale', 'offset')"),
         }
     }
 
-    return Ok((from, to, at));
+    return Ok(result);
 }
 
-fn parse_bitfield(field:&Field) -> Result<BitField, &str> {
+fn parse_bitfield(field:&Field) -> Result<BitField, &'static str> {
     let ty = field.ty.clone();
 
     let meta_item_params = get_register_item_params(&field)?;
 
-    let (from, to, at) = parse_register_item_params(meta_item_params)?;
+    let params = parse_register_item_params(&meta_item_params)?;
+    let (from, to, at) = (params.from, params.to, params.at);
+
+    if params.bits.is_some() && (from.is_some() || to.is_some() || at.is_some()) {
+        return Err("select 'bits' or 'at'/'from'/'to' parameters, not both");
+    }
 
     if (from.is_some() || to.is_some()) && at.is_some() {
         return Err("select 'at' or 'from'/'to' parameters, not both");
@@ -304,37 +1440,171 @@ fn parse_bitfield(field:&Field) -> Result<BitField, &str> {
         return Err("select 'from' and 'to' parameters together");
     }
 
-    if from.is_none() && to.is_none() && at.is_none() {
-        return Err("select bit parameters (use #[bitfield(at=x or from=x to=y)])");
+    if from.is_none() && to.is_none() && at.is_none() && params.bits.is_none() {
+        return Err("select bit parameters (use #[bitfield(at=x or from=x to=y or bits=[...])])");
+    }
+
+    if from > to {
+        return Err("'from' should not be greater than 'to'");
     }
 
-    let position: BitFieldPosition = if from.is_some() && to.is_some() {
+    let position: BitFieldPosition = if let Some(pieces) = params.bits {
+        BitFieldPosition::Split(pieces)
+    } else if from.is_some() && to.is_some() {
         BitFieldPosition::Range(std::ops::Range{start: from.unwrap(), end: to.unwrap() + 1})
     } else {
         BitFieldPosition::Single(at.unwrap())
     };
 
     let ident = field.ident.clone().unwrap();
+    let docs = doc_attrs(&field.attrs);
+    let access = params.access.unwrap_or(Access::ReadWrite);
+
+    if params.big_endian && position.len() % 8 != 0 {
+        return Err("big endian fields should be a whole number of bytes");
+    }
+
+    let scale = match (params.scale, params.offset) {
+        (None, None) => None,
+        (scale, offset) => {
+            if integer_bits(&ty).is_none() {
+                return Err("scale and offset need an unsigned integer field");
+            }
+            if scale == Some(0.0) {
+                return Err("scale should not be zero");
+            }
+            Some((scale.unwrap_or(1.0), offset.unwrap_or(0.0)))
+        }
+    };
+
+    Ok(BitField {position, ident, ty, access, docs, range: params.range, overlay: params.overlay, big_endian: params.big_endian, scale})
+}
+
+// fields own their bits, only overlay fields may reuse bits of other fields
+fn check_overlaps(bitfields: &[BitField]) -> Result<(), &'static str> {
+    let owners: Vec<&BitField> = bitfields.iter().filter(|bitfield| !bitfield.overlay).collect();
+    for (i, bitfield) in owners.iter().enumerate() {
+        for other in &owners[..i] {
+            let overlaps = bitfield.position.pieces().iter().any(|piece| {
+                other.position.pieces().iter().any(|other| other.start < piece.end && piece.start < other.end)
+            });
+            if overlaps {
+                return Err("fields overlap (mark intentional aliases with 'overlay')");
+            }
+        }
+    }
+    return Ok(());
+}
+
+// every variant struct gets the mode field, its accessors may not be generated twice
+fn check_variant_field(bitfield: &BitField) -> Result<(), &'static str> {
+    if bitfield.ident == "mode" {
+        return Err("variant field 'mode' clashes with the mode field of the register, rename it");
+    }
+    return Ok(());
+}
+
+// #[mode(at = x)] or #[mode(from = x, to = y)] of an enum register
+fn parse_mode(attrs: &Vec<Attribute>) -> Result<BitFieldPosition, &'static str> {
+    let attr = match attrs.iter().find(|attr| attr.path().is_ident("mode")) {
+        Some(attr) => attr,
+        None => return Err("mode() meta not found. select the mode field of an enum register (use #[mode(from=x, to=y)])"),
+    };
+    let metas = attr.parse_args_with(MetaList::parse_terminated).map_err(|_| "invalid mode params")?;
+    let params = parse_register_item_params(&metas)?;
+    if params.bits.is_some() || params.access.is_some() || params.range.is_some() || params.overlay {
+        return Err("mode supports only 'at' or 'from'/'to'");
+    }
+
+    return match (params.from, params.to, params.at) {
+        (None, None, Some(at)) => Ok(BitFieldPosition::Single(at)),
+        (Some(from), Some(to), None) if from <= to => Ok(BitFieldPosition::Range(from..to + 1)),
+        _ => Err("select mode bits (use #[mode(at=x or from=x, to=y)])"),
+    };
+}
 
-    Ok(BitField {position, ident, ty})
+// #[checksum(kind = "crc8", over = 0..=23, at = 24..=31)] of a register
+fn parse_checksum(attrs: &Vec<Attribute>) -> Result<Option<Checksum>, &'static str> {
+    let attr = match attrs.iter().find(|attr| attr.path().is_ident("checksum")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    let metas = attr.parse_args_with(MetaList::parse_terminated).map_err(|_| "invalid checksum params")?;
+
+    let (mut kind, mut over, mut at, mut big_endian) = (None, None, None, false);
+    for meta in &metas {
+        let nv = match meta {
+            &Meta::NameValue(ref nv) => nv,
+            _ => return Err("unsupported checksum param (use name = value)"),
+        };
+        match (path_name(&nv.path).as_str(), &nv.value) {
+            ("kind", &Expr::Lit(ExprLit { lit: Lit::Str(ref value), .. })) => {
+                kind = Some(match value.value().as_str() {
+                    "parity_even" => ChecksumKind::ParityEven,
+                    "parity_odd" => ChecksumKind::ParityOdd,
+                    "crc8" => ChecksumKind::Crc8,
+                    "crc16" => ChecksumKind::Crc16,
+                    _ => return Err("unsupported checksum kind (use 'parity_even', 'parity_odd', 'crc8' or 'crc16')"),
+                });
+            }
+            ("over", value) => over = Some(parse_piece(value)?),
+            ("at", value) => at = Some(parse_piece(value)?),
+            ("endian", &Expr::Lit(ExprLit { lit: Lit::Str(ref value), .. })) => {
+                big_endian = match value.value().as_str() {
+                    "little" => false,
+                    "big" => true,
+                    _ => return Err("unsupported checksum endian (use 'little' or 'big')"),
+                };
+            }
+            _ => return Err("unsupported checksum param name (use 'kind', 'over', 'at', 'endian')"),
+        }
+    }
+
+    let (kind, over, at) = match (kind, over, at) {
+        (Some(kind), Some(over), Some(at)) => (kind, over.start as usize..over.end as usize, at.start as usize..at.end as usize),
+        _ => return Err("select checksum kind, over and at (use #[checksum(kind = \"crc8\", over = 0..=23, at = 24..=31)])"),
+    };
+
+    let width = match kind {
+        ChecksumKind::ParityEven | ChecksumKind::ParityOdd => 1,
+        ChecksumKind::Crc8 => 8,
+        ChecksumKind::Crc16 => 16,
+    };
+    if at.end - at.start != width {
+        return Err("checksum bits should match the checksum kind (1 bit for parity, 8 for crc8, 16 for crc16)");
+    }
+    if width > 1 && (over.start % 8 != 0 || over.end % 8 != 0) {
+        return Err("crc should be computed over whole bytes");
+    }
+    if over.start < at.end && at.start < over.end {
+        return Err("checksum should not cover its own bits");
+    }
+    if big_endian && (kind != ChecksumKind::Crc16 || at.start % 8 != 0) {
+        return Err("endian = \"big\" needs a crc16 stored at whole bytes");
+    }
+
+    return Ok(Some(Checksum { kind, over, at, big_endian }));
 }
 
 #[proc_macro_attribute]
-pub fn register(_: TokenStream, input: TokenStream) -> TokenStream {
-    let s = input.to_string();
-    let ast = parse_derive_input(&s).unwrap();
+pub fn register(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut params = parse_register_params(&args.to_string()).unwrap();
 
-    // println!("{}", s);
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    params.checksum = parse_checksum(&ast.attrs).unwrap();
 
-    let fields = match ast.body {
-        Body::Enum(_) => panic!("enum not supported"),
-        Body::Struct(x) => match x {
-            VariantData::Struct(fields) => fields,
+    let name = &ast.ident;
+    let docs = doc_attrs(&ast.attrs);
+
+    let fields = match ast.data {
+        Data::Enum(ref data) => return output_enum(name, &params, &docs, &ast.attrs, data).into(),
+        Data::Union(_) => panic!("union not supported"),
+        Data::Struct(x) => match x.fields {
+            Fields::Named(fields) => fields.named,
             _ => panic!("tuple and unit not supported")
         }
     };
 
-    #[derive(Debug)]
     let mut bitfields: Vec<BitField> = vec![];
 
     for field in &fields {
@@ -342,12 +1612,11 @@ pub fn register(_: TokenStream, input: TokenStream) -> TokenStream {
         bitfields.push(bitfield);
     }
 
-    let name = &ast.ident;
+    check_overlaps(&bitfields).unwrap();
 
-    return output_struct(name, &bitfields).parse().unwrap();
+    return output_struct(name, &params, &docs, &bitfields).into();
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -440,25 +1709,47 @@ mod tests {
         let from = quote!{arr};
 
         let res = emit_read_single_byte(from.clone(), 0, 8);
-        assert_eq!(res, quote!{ #from[0usize] & 255u8 });
+        assert_eq!(res.to_string(), quote!{ #from[0usize] & 255u8 }.to_string());
 
         let left_mask : u8 = 0b10000000;
         let res = emit_read_single_byte(from.clone(), 7, 1);
-        assert_eq!(res, quote!{ (#from[0usize] & #left_mask) >> 7usize });
+        assert_eq!(res.to_string(), quote!{ (#from[0usize] & #left_mask) >> 7usize }.to_string());
 
         let left_mask : u8 = 0b00011100;
         let res = emit_read_single_byte(from.clone(), 2, 3);
-        assert_eq!(res, quote!{ (#from[0usize] & #left_mask) >> 2usize });
+        assert_eq!(res.to_string(), quote!{ (#from[0usize] & #left_mask) >> 2usize }.to_string());
 
         let left_mask : u8 = 0b11000000;
         let right_mask : u8 = 0b00000011;
         let res = emit_read_single_byte(from.clone(), 6, 4);
-        assert_eq!(res, quote!{ ((#from[0usize] & #left_mask) >> 6usize) | ((#from[1usize] & #right_mask) << 2usize) });
+        assert_eq!(res.to_string(), quote!{ ((#from[0usize] & #left_mask) >> 6usize) | ((#from[1usize] & #right_mask) << 2usize) }.to_string());
 
         let left_mask : u8 = 0b11111110;
         let right_mask : u8 = 0b00000001;
         let res = emit_read_single_byte(from.clone(), 9, 8);
-        assert_eq!(res, quote!{ ((#from[1usize] & #left_mask) >> 1usize) | ((#from[2usize] & #right_mask) << 7usize) });
+        assert_eq!(res.to_string(), quote!{ ((#from[1usize] & #left_mask) >> 1usize) | ((#from[2usize] & #right_mask) << 7usize) }.to_string());
+    }
+
+    #[test]
+    fn emit_word_access_test() {
+        let (getter, write) = emit_word_access(&BitFieldPosition::Range(12..20)).unwrap();
+        assert_eq!(getter.to_string(), quote! {
+            let word = (u16::from_le_bytes([self.0[1usize], self.0[2usize]]) >> 4usize) & 255;
+            let word_bytes = word.to_le_bytes();
+            let value_array: [u8;1usize] = [word_bytes[0usize]];
+        }.to_string());
+        assert_eq!(write.to_string(), quote! {
+            let value = u16::from_le_bytes([value_array[0usize], 0]) & 255;
+            let word = (u16::from_le_bytes([self.0[1usize], self.0[2usize]]) & 61455) | (value << 4usize);
+            let word_bytes = word.to_le_bytes();
+            self.0[1usize..3usize].copy_from_slice(&word_bytes[..2usize]);
+        }.to_string());
+
+        let (getter, _) = emit_word_access(&BitFieldPosition::Range(8..32)).unwrap();
+        assert!(getter.to_string().contains(&quote! { u32::from_le_bytes([self.0[1usize], self.0[2usize], self.0[3usize], 0]) }.to_string()));
+
+        assert!(emit_word_access(&BitFieldPosition::Range(4..68)).is_none());
+        assert!(emit_word_access(&BitFieldPosition::Split(vec![0..4, 8..12])).is_none());
     }
 
     #[test]
@@ -467,42 +1758,210 @@ mod tests {
         let to = quote!{arr};
 
         let res = emit_write_single_byte(to.clone(), from.clone(), 0, 8);
-        assert_eq!(res, quote!{ #to[0usize] = (#from & 255u8) | (#to[0usize] & 0u8) });
+        assert_eq!(res.to_string(), quote!{ #to[0usize] = (#from & 255u8) | (#to[0usize] & 0u8) }.to_string());
 
         let left_src_mask : u8 = 0b00000011;
         let left_dst_mask : u8 = 0b11111100;
         let res = emit_write_single_byte(to.clone(), from.clone(), 0, 2);
-        assert_eq!(res, quote!{ #to[0usize] = (#from & #left_src_mask) | (#to[0usize] & #left_dst_mask) });
+        assert_eq!(res.to_string(), quote!{ #to[0usize] = (#from & #left_src_mask) | (#to[0usize] & #left_dst_mask) }.to_string());
 
         let left_src_mask : u8 = 0b10000000;
         let left_dst_mask : u8 = 0b01111111;
         let res = emit_write_single_byte(to.clone(), from.clone(), 7, 1);
-        assert_eq!(res, quote!{ #to[0usize] = ((#from << 7usize) & #left_src_mask) | (#to[0usize] & #left_dst_mask) });
+        assert_eq!(res.to_string(), quote!{ #to[0usize] = ((#from << 7usize) & #left_src_mask) | (#to[0usize] & #left_dst_mask) }.to_string());
 
         let left_src_mask : u8 = 0b00011100;
         let left_dst_mask : u8 = 0b11100011;
         let res = emit_write_single_byte(to.clone(), from.clone(), 2, 3);
-        assert_eq!(res, quote!{ #to[0usize] = ((#from << 2usize) & #left_src_mask) | (#to[0usize] & #left_dst_mask) });
+        assert_eq!(res.to_string(), quote!{ #to[0usize] = ((#from << 2usize) & #left_src_mask) | (#to[0usize] & #left_dst_mask) }.to_string());
 
         let left_src_mask : u8 = 0b11000000;
         let left_dst_mask : u8 = 0b00111111;
         let right_src_mask : u8 = 0b00000011;
         let right_dst_mask : u8 = 0b11111100;
         let res = emit_write_single_byte(to.clone(), from.clone(), 6, 4);
-        assert_eq!(res, quote!{
+        assert_eq!(res.to_string(), quote!{
             #to[0usize] = ((#from << 6usize) & #left_src_mask)  | (#to[0usize] & #left_dst_mask);
             #to[1usize] = ((#from >> 2usize) & #right_src_mask) | (#to[1usize] & #right_dst_mask);
-        });
+        }.to_string());
 
         let left_src_mask : u8 = 0b11111110;
         let left_dst_mask : u8 = 0b00000001;
         let right_src_mask : u8 = 0b00000001;
         let right_dst_mask : u8 = 0b11111110;
         let res = emit_write_single_byte(to.clone(), from.clone(), 9, 8);
-        assert_eq!(res, quote!{
+        assert_eq!(res.to_string(), quote!{
             #to[1usize] = ((#from << 1usize) & #left_src_mask)  | (#to[1usize] & #left_dst_mask);
             #to[2usize] = ((#from >> 7usize) & #right_src_mask) | (#to[2usize] & #right_dst_mask);
-        });
+        }.to_string());
+    }
+
+    #[test]
+    fn register_params_test() {
+        assert_eq!(parse_register_params("").unwrap().address, None);
+        assert_eq!(parse_register_params("address = 26").unwrap().address, Some(26));
+        assert_eq!(parse_register_params("address = 0x1A").unwrap().address, Some(0x1A));
+
+        assert!(parse_register_params("address = 0x100000000").is_err());
+        assert!(parse_register_params("addr = 1").is_err());
+        assert!(parse_register_params("address").is_err());
+
+        let params = parse_register_params("address = 4, size = 4, reset = 0xFF00").unwrap();
+        assert_eq!(params.address, Some(4));
+        assert_eq!(params.size, Some(4));
+        assert_eq!(params.reset, Some(0xFF00));
+
+        assert!(parse_register_params("size = 0").is_err());
+
+        assert_eq!(parse_register_params("address = 4").unwrap().checked, false);
+        assert_eq!(parse_register_params("checked, address = 4").unwrap().checked, true);
+        assert!(parse_register_params("checked = 1").is_err());
+        assert_eq!(parse_register_params("bytewise").unwrap().bytewise, true);
+    }
+
+    fn parse_field(field: Tokens) -> Result<BitField, &'static str> {
+        let field = Field::parse_named.parse2(field).unwrap();
+        return parse_bitfield(&field);
+    }
+
+    #[test]
+    fn bitfield_params_test() {
+        let field = parse_field(quote! { #[bitfield(from = 0, to = 3, range = 1..=12)] divider: u8 }).unwrap();
+        assert_eq!(field.position.len(), 4);
+        assert_eq!(field.range.map(|range| quote!(#range).to_string()), Some(quote!(1..=12).to_string()));
+
+        let field = parse_field(quote! { #[bitfield(at = 2, access = "read-only")] ready: bool }).unwrap();
+        assert_eq!(field.access, Access::ReadOnly);
+        assert!(field.range.is_none());
+
+        assert!(parse_field(quote! { #[bitfield(at = 2, range = 12)] ready: bool }).is_err());
+        assert!(parse_field(quote! { #[bitfield(at = 2, bits = 1)] ready: bool }).is_err());
+        assert!(parse_field(quote! { ready: bool }).is_err());
+
+        let field = parse_field(quote! { #[bitfield(at = 300)] flag: bool }).unwrap();
+        assert_eq!(field.position.first_bit(), 300);
+        let field = parse_field(quote! { #[bitfield(from = 250, to = 4095)] blob: u8 }).unwrap();
+        assert_eq!(field.position.len(), 3846);
+
+        assert!(parse_field(quote! { #[bitfield(at = 4294967296)] flag: bool }).is_err());
+        assert!(parse_field(quote! { #[bitfield(at = 4294967295)] flag: bool }).is_err());
+        assert!(parse_field(quote! { #[bitfield(at = -1)] flag: bool }).is_err());
+        assert!(parse_field(quote! { #[bitfield(at = "1")] flag: bool }).is_err());
+        assert!(parse_field(quote! { #[bitfield(from = 8, to = 7)] value: u8 }).is_err());
+    }
+
+    #[test]
+    fn split_field_params_test() {
+        let field = parse_field(quote! { #[bitfield(bits = [0..=3, 8..12, 15])] value: u16 }).unwrap();
+        assert_eq!(field.position.pieces(), vec![0..4, 8..12, 15..16]);
+        assert_eq!(field.position.len(), 9);
+        assert_eq!((field.position.first_bit(), field.position.last_bit()), (0, 15));
+        assert_eq!(field.position.segments(), vec![
+            Segment { value_byte: 0, value_offset: 0, from_bit: 0, bit_length: 4 },
+            Segment { value_byte: 0, value_offset: 4, from_bit: 8, bit_length: 4 },
+            Segment { value_byte: 1, value_offset: 0, from_bit: 15, bit_length: 1 },
+        ]);
+
+        // pieces are listed least significant first, not in register order
+        let field = parse_field(quote! { #[bitfield(bits = [12..=13, 0..=6])] value: u8 }).unwrap();
+        assert_eq!(field.position.segments(), vec![
+            Segment { value_byte: 0, value_offset: 0, from_bit: 12, bit_length: 2 },
+            Segment { value_byte: 0, value_offset: 2, from_bit: 0, bit_length: 6 },
+            Segment { value_byte: 1, value_offset: 0, from_bit: 6, bit_length: 1 },
+        ]);
+
+        assert!(parse_field(quote! { #[bitfield(bits = [])] value: u8 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(bits = [0..=3, 2..=5])] value: u8 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(bits = [3..3])] value: u8 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(bits = [0..])] value: u8 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(at = 0, bits = [1..=2])] value: u8 }).is_err());
+    }
+
+    #[test]
+    fn overlay_test() {
+        let field = parse_field(quote! { #[bitfield(from = 0, to = 7, overlay)] raw: u8 }).unwrap();
+        assert!(field.overlay);
+        assert!(parse_field(quote! { #[bitfield(from = 0, to = 7, alias)] raw: u8 }).is_err());
+
+        let raw = parse_field(quote! { #[bitfield(from = 0, to = 7)] raw: u8 }).unwrap();
+        let low = parse_field(quote! { #[bitfield(from = 0, to = 3)] low: u8 }).unwrap();
+        let high = parse_field(quote! { #[bitfield(bits = [4..=5, 8..=9])] high: u8 }).unwrap();
+        assert!(check_overlaps(&[low, high]).is_ok());
+
+        let low = parse_field(quote! { #[bitfield(from = 0, to = 3)] low: u8 }).unwrap();
+        assert!(check_overlaps(&[raw, low]).is_err());
+
+        let raw = parse_field(quote! { #[bitfield(from = 0, to = 7, overlay)] raw: u8 }).unwrap();
+        let low = parse_field(quote! { #[bitfield(from = 0, to = 3)] low: u8 }).unwrap();
+        let high = parse_field(quote! { #[bitfield(at = 3)] high: bool }).unwrap();
+        assert!(check_overlaps(&[raw, high, low]).is_err());
+    }
+
+    #[test]
+    fn mode_params_test() {
+        let item: syn::ItemEnum = syn::parse2(quote! { #[mode(from = 0, to = 1)] enum Channel {} }).unwrap();
+        assert_eq!(parse_mode(&item.attrs).unwrap().len(), 2);
+        let item: syn::ItemEnum = syn::parse2(quote! { #[mode(at = 7)] enum Channel {} }).unwrap();
+        assert_eq!(parse_mode(&item.attrs).unwrap().first_bit(), 7);
+
+        let item: syn::ItemEnum = syn::parse2(quote! { enum Channel {} }).unwrap();
+        assert!(parse_mode(&item.attrs).is_err());
+        let item: syn::ItemEnum = syn::parse2(quote! { #[mode(bits = [0..=1])] enum Channel {} }).unwrap();
+        assert!(parse_mode(&item.attrs).is_err());
+        let item: syn::ItemEnum = syn::parse2(quote! { #[mode(from = 2)] enum Channel {} }).unwrap();
+        assert!(parse_mode(&item.attrs).is_err());
+
+        assert!(check_variant_field(&parse_field(quote! { #[bitfield(from = 2, to = 3)] gain: u8 }).unwrap()).is_ok());
+        assert!(check_variant_field(&parse_field(quote! { #[bitfield(from = 2, to = 3)] mode: u8 }).unwrap()).is_err());
+    }
+
+    #[test]
+    fn endian_params_test() {
+        assert!(!parse_field(quote! { #[bitfield(from = 8, to = 23)] value: u16 }).unwrap().big_endian);
+        assert!(!parse_field(quote! { #[bitfield(from = 8, to = 23, endian = "little")] value: u16 }).unwrap().big_endian);
+        assert!(parse_field(quote! { #[bitfield(from = 8, to = 23, endian = "big")] value: u16 }).unwrap().big_endian);
+
+        assert!(parse_field(quote! { #[bitfield(from = 8, to = 23, endian = "middle")] value: u16 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(from = 8, to = 19, endian = "big")] value: u16 }).is_err());
+    }
+
+    #[test]
+    fn scale_params_test() {
+        let field = parse_field(quote! { #[bitfield(from = 0, to = 9, scale = 0.5, offset = -40.0)] temperature: u16 }).unwrap();
+        assert_eq!(field.scale, Some((0.5, -40.0)));
+        let field = parse_field(quote! { #[bitfield(from = 0, to = 9, offset = -40)] temperature: u16 }).unwrap();
+        assert_eq!(field.scale, Some((1.0, -40.0)));
+        assert!(parse_field(quote! { #[bitfield(from = 0, to = 9)] temperature: u16 }).unwrap().scale.is_none());
+
+        assert!(parse_field(quote! { #[bitfield(from = 0, to = 9, scale = 0)] temperature: u16 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(from = 0, to = 9, scale = "0.5")] temperature: u16 }).is_err());
+        assert!(parse_field(quote! { #[bitfield(at = 0, scale = 2)] flag: bool }).is_err());
+    }
+
+    #[test]
+    fn checksum_params_test() {
+        let checksum = |tokens: Tokens| {
+            let item: syn::ItemStruct = syn::parse2(tokens).unwrap();
+            return parse_checksum(&item.attrs);
+        };
+
+        let crc = checksum(quote! { #[checksum(kind = "crc8", over = 0..=23, at = 24..=31)] struct Frame {} }).unwrap().unwrap();
+        assert_eq!((crc.kind, crc.over, crc.at), (ChecksumKind::Crc8, 0..24, 24..32));
+        let parity = checksum(quote! { #[checksum(kind = "parity_odd", over = 1..=6, at = 7)] struct Frame {} }).unwrap().unwrap();
+        assert_eq!((parity.kind, parity.over, parity.at), (ChecksumKind::ParityOdd, 1..7, 7..8));
+        assert!(checksum(quote! { struct Frame {} }).unwrap().is_none());
+
+        assert!(checksum(quote! { #[checksum(kind = "crc32", over = 0..=23, at = 24..=31)] struct Frame {} }).is_err());
+        assert!(checksum(quote! { #[checksum(kind = "crc8", over = 0..=23)] struct Frame {} }).is_err());
+        assert!(checksum(quote! { #[checksum(kind = "crc8", over = 0..=23, at = 24..=39)] struct Frame {} }).is_err());
+        assert!(checksum(quote! { #[checksum(kind = "crc16", over = 4..=23, at = 24..=39)] struct Frame {} }).is_err());
+        let big = checksum(quote! { #[checksum(kind = "crc16", over = 0..=15, at = 16..=31, endian = "big")] struct Frame {} }).unwrap().unwrap();
+        assert_eq!((big.kind, big.big_endian), (ChecksumKind::Crc16, true));
+        assert!(!crc.big_endian);
+        assert!(checksum(quote! { #[checksum(kind = "crc8", over = 0..=23, at = 24..=31, endian = "big")] struct Frame {} }).is_err());
+        assert!(checksum(quote! { #[checksum(kind = "crc16", over = 0..=15, at = 17..=32, endian = "big")] struct Frame {} }).is_err());
+        assert!(checksum(quote! { #[checksum(kind = "crc16", over = 0..=15, at = 16..=31, endian = "middle")] struct Frame {} }).is_err());
+        assert!(checksum(quote! { #[checksum(kind = "parity_even", over = 0..=7, at = 7)] struct Frame {} }).is_err());
     }
 
     #[test]
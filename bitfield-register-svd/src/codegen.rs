@@ -0,0 +1,336 @@
+use std::collections::{HashMap, HashSet};
+
+use super::svd::{Access, Device, EnumeratedValues, Field, Peripheral, Register};
+use super::Error;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+fn sanitize(ident: String) -> String {
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("_{}", ident);
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        return format!("{}_", ident);
+    }
+    return ident;
+}
+
+// CTRL_EN, CtrlEn -> ctrl_en
+pub fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !result.ends_with('_') {
+                result.push('_');
+            }
+        } else {
+            if c.is_ascii_uppercase() && previous.map_or(false, |p| p.is_ascii_lowercase()) {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        }
+        previous = Some(c);
+    }
+    return sanitize(result.trim_matches('_').to_string());
+}
+
+// CTRL_EN, oneShot -> CtrlEn, OneShot
+pub fn camel_case(name: &str) -> String {
+    let mut result = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|word| !word.is_empty()) {
+        let mut chars = word.chars();
+        let first = chars.next().unwrap();
+        result.push(first.to_ascii_uppercase());
+        let rest: String = chars.collect();
+        if word.chars().any(|c| c.is_ascii_lowercase()) {
+            result.push_str(&rest);
+        } else {
+            result.push_str(&rest.to_ascii_lowercase());
+        }
+    }
+    return sanitize(result);
+}
+
+fn raw_type(byte_len: u32) -> &'static str {
+    return match byte_len {
+        1 => "u8",
+        2 => "u16",
+        _ => "u32",
+    };
+}
+
+fn access_name(access: Access) -> &'static str {
+    return match access {
+        Access::ReadWrite => "read-write",
+        Access::ReadOnly => "read-only",
+        Access::WriteOnly => "write-only",
+    };
+}
+
+struct Source {
+    text: String,
+    indent: usize,
+}
+
+impl Source {
+    fn line(&mut self, line: &str) {
+        if !line.is_empty() {
+            for _ in 0..self.indent {
+                self.text.push_str("    ");
+            }
+            self.text.push_str(line);
+        }
+        self.text.push('\n');
+    }
+
+    fn doc(&mut self, description: &Option<String>) {
+        if let &Some(ref description) = description {
+            self.line(&format!("/// {}", description));
+        }
+    }
+}
+
+pub fn generate_device(device: &Device) -> Result<String, Error> {
+    let mut source = Source { text: String::new(), indent: 0 };
+
+    source.line(&format!("// Generated by bitfield-register-svd from {}, do not edit.", device.name));
+    if let Some(ref description) = device.description {
+        source.line(&format!("// {}", description));
+    }
+
+    for peripheral in &device.peripherals {
+        source.line("");
+        generate_peripheral(&mut source, peripheral)?;
+    }
+
+    return Ok(source.text);
+}
+
+fn generate_peripheral(source: &mut Source, peripheral: &Peripheral) -> Result<(), Error> {
+    source.doc(&peripheral.description);
+    source.line(&format!("pub mod {} {{", snake_case(&peripheral.name)));
+    source.indent += 1;
+    source.line("use bitfield_register_macro::register;");
+
+    let mut enums: HashMap<String, (u32, EnumeratedValues)> = HashMap::new();
+    for register in &peripheral.registers {
+        source.line("");
+        generate_register(source, peripheral, register, &mut enums)?;
+    }
+
+    source.indent -= 1;
+    source.line("}");
+    return Ok(());
+}
+
+// enums already emitted in the peripheral module, by name, with the field width they were generated for
+type Enums = HashMap<String, (u32, EnumeratedValues)>;
+
+fn generate_register(source: &mut Source, peripheral: &Peripheral, register: &Register, emitted_enums: &mut Enums) -> Result<(), Error> {
+    let name = camel_case(&register.name);
+
+    let address = peripheral.base_address + register.address_offset;
+    if address > u32::max_value() as u64 {
+        return Err(Error::Unsupported(format!("register {} at address 0x{:X}", register.name, address)));
+    }
+
+    // registers without fields get a single field holding the whole value
+    let fields = if register.fields.is_empty() {
+        vec![Field {
+            name: String::from("value"),
+            description: None,
+            bit_offset: 0,
+            bit_width: register.size,
+            access: register.access,
+            enumerated_values: None,
+        }]
+    } else {
+        register.fields.clone()
+    };
+
+    let mut enums = vec![];
+    let mut field_lines = vec![];
+    for field in &fields {
+        if field.bit_width == 0 || field.bit_offset + field.bit_width > register.size {
+            return Err(Error::Svd(format!("field {}.{} does not fit register", register.name, field.name)));
+        }
+        if field.bit_width > 32 {
+            return Err(Error::Unsupported(format!("field {}.{} wider than 32 bits", register.name, field.name)));
+        }
+
+        let ty = match field.enumerated_values {
+            Some(ref values) => {
+                // identical enums, e.g. of register array elements, are emitted once
+                let signature = (field.bit_width, values.clone());
+                let same = |enum_name: &String| emitted_enums.get(enum_name).map(|emitted| *emitted == signature);
+
+                let mut enum_name = values.name.as_ref().map(|name| camel_case(name)).unwrap_or_default();
+                if enum_name.is_empty() || same(&enum_name) == Some(false) {
+                    enum_name = format!("{}{}", camel_case(&register.base_name), camel_case(&field.name));
+                }
+                match same(&enum_name) {
+                    Some(true) => {}
+                    Some(false) => {
+                        return Err(Error::Unsupported(format!("duplicate enum {} for field {}.{}", enum_name, register.name, field.name)));
+                    }
+                    None => {
+                        emitted_enums.insert(enum_name.clone(), signature);
+                        enums.push((enum_name.clone(), field, values));
+                    }
+                }
+                enum_name
+            }
+            None => String::from(match field.bit_width {
+                1 => "bool",
+                2..=8 => "u8",
+                9..=16 => "u16",
+                _ => "u32",
+            }),
+        };
+
+        let position = if field.bit_width == 1 {
+            format!("at = {}", field.bit_offset)
+        } else {
+            format!("from = {}, to = {}", field.bit_offset, field.bit_offset + field.bit_width - 1)
+        };
+        let access = if field.access == Access::ReadWrite {
+            String::new()
+        } else {
+            format!(", access = \"{}\"", access_name(field.access))
+        };
+
+        field_lines.push((field, format!("#[bitfield({}{})]", position, access), format!("{}: {},", snake_case(&field.name), ty)));
+    }
+
+    source.doc(&register.description);
+    source.line(&format!(
+        "#[register(address = 0x{:08X}, size = {}, reset = 0x{:0width$X})]",
+        address, register.size / 8, register.reset_value, width = (register.size / 4) as usize
+    ));
+    source.line(&format!("pub struct {} {{", name));
+    source.indent += 1;
+    for (field, attribute, declaration) in field_lines {
+        source.doc(&field.description);
+        source.line(&attribute);
+        source.line(&declaration);
+    }
+    source.indent -= 1;
+    source.line("}");
+
+    for (enum_name, field, values) in enums {
+        source.line("");
+        generate_enum(source, &enum_name, field, values)?;
+    }
+
+    return Ok(());
+}
+
+fn generate_enum(source: &mut Source, name: &str, field: &Field, values: &EnumeratedValues) -> Result<(), Error> {
+    let byte_len = (field.bit_width + 7) / 8;
+    let raw = raw_type(byte_len);
+    let array = format!("[u8;{}]", byte_len);
+
+    let mut variants = vec![];
+    let mut seen_values = HashSet::new();
+    for value in &values.values {
+        if field.bit_width < 64 && value.value >> field.bit_width != 0 {
+            return Err(Error::Svd(format!("enumerated value {} does not fit field {}", value.name, field.name)));
+        }
+        if !seen_values.insert(value.value) {
+            return Err(Error::Svd(format!("duplicate enumerated value {} of field {}", value.value, field.name)));
+        }
+        variants.push((camel_case(&value.name), value));
+    }
+    // the fallback variant keeps values without a name, it is not needed when every value is named
+    let exhaustive = field.bit_width < 64 && variants.len() as u64 == 1u64 << field.bit_width;
+
+    source.doc(&field.description);
+    source.line("#[derive(Debug, Clone, Copy, PartialEq, Eq)]");
+    source.line(&format!("pub enum {} {{", name));
+    source.indent += 1;
+    for &(ref variant, value) in &variants {
+        source.doc(&value.description);
+        source.line(&format!("{},", variant));
+    }
+    if !exhaustive {
+        source.line(&format!("Unknown({}),", raw));
+    }
+    source.indent -= 1;
+    source.line("}");
+
+    source.line("");
+    source.line(&format!("impl ::bitfield_register::FromBitfield<{}> for {} {{", array, name));
+    source.indent += 1;
+    source.line(&format!("fn from_bitfield(array: {}) -> Self {{", array));
+    source.indent += 1;
+    source.line(&format!("return match <{} as ::bitfield_register::FromBitfield<{}>>::from_bitfield(array) {{", raw, array));
+    source.indent += 1;
+    for &(ref variant, value) in &variants {
+        source.line(&format!("{} => {}::{},", value.value, name, variant));
+    }
+    if exhaustive {
+        source.line("_ => unreachable!(),");
+    } else {
+        source.line(&format!("value => {}::Unknown(value),", name));
+    }
+    source.indent -= 1;
+    source.line("};");
+    source.indent -= 1;
+    source.line("}");
+    source.indent -= 1;
+    source.line("}");
+
+    source.line("");
+    source.line(&format!("impl ::bitfield_register::IntoBitfield<{}> for {} {{", array, name));
+    source.indent += 1;
+    source.line(&format!("fn into_bitfield(self) -> {} {{", array));
+    source.indent += 1;
+    source.line(&format!("let value: {} = match self {{", raw));
+    source.indent += 1;
+    for &(ref variant, value) in &variants {
+        source.line(&format!("{}::{} => {},", name, variant, value.value));
+    }
+    if !exhaustive {
+        source.line(&format!("{}::Unknown(value) => value,", name));
+    }
+    source.indent -= 1;
+    source.line("};");
+    source.line("return ::bitfield_register::IntoBitfield::into_bitfield(value);");
+    source.indent -= 1;
+    source.line("}");
+    source.indent -= 1;
+    source.line("}");
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_test() {
+        assert_eq!(snake_case("CTRL"), "ctrl");
+        assert_eq!(snake_case("CTRL_EN"), "ctrl_en");
+        assert_eq!(snake_case("CountValue"), "count_value");
+        assert_eq!(snake_case("TIMER0"), "timer0");
+        assert_eq!(snake_case("DIR[3]"), "dir_3");
+        assert_eq!(snake_case("TYPE"), "type_");
+        assert_eq!(snake_case("3V3"), "_3v3");
+    }
+
+    #[test]
+    fn camel_case_test() {
+        assert_eq!(camel_case("CTRL"), "Ctrl");
+        assert_eq!(camel_case("CTRL_EN"), "CtrlEn");
+        assert_eq!(camel_case("oneShot"), "OneShot");
+        assert_eq!(camel_case("TIMER0_MODE"), "Timer0Mode");
+        assert_eq!(camel_case("8BIT"), "_8bit");
+    }
+}
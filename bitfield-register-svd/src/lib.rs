@@ -0,0 +1,68 @@
+// Reads a CMSIS-SVD register map and emits Rust source with #[register] definitions.
+//
+// From build.rs:
+//
+//     bitfield_register_svd::generate_file("device.svd", out_dir.join("device.rs")).unwrap();
+//
+// and in the crate root:
+//
+//     include!(concat!(env!("OUT_DIR"), "/device.rs"));
+//
+// Generated code uses both bitfield_register and bitfield_register_macro crates.
+
+extern crate roxmltree;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+mod codegen;
+pub mod svd;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Xml(roxmltree::Error),
+    // malformed or inconsistent SVD
+    Svd(String),
+    // valid SVD that can not be expressed with #[register]
+    Unsupported(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            &Error::Io(ref err) => write!(f, "{}", err),
+            &Error::Xml(ref err) => write!(f, "invalid xml: {}", err),
+            &Error::Svd(ref message) => write!(f, "invalid svd: {}", message),
+            &Error::Unsupported(ref message) => write!(f, "unsupported: {}", message),
+        };
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        return Error::Io(err);
+    }
+}
+
+impl From<roxmltree::Error> for Error {
+    fn from(err: roxmltree::Error) -> Self {
+        return Error::Xml(err);
+    }
+}
+
+pub fn generate(svd: &str) -> Result<String, Error> {
+    let device = svd::parse_device(svd)?;
+    return codegen::generate_device(&device);
+}
+
+pub fn generate_file<P: AsRef<Path>, Q: AsRef<Path>>(input: P, output: Q) -> Result<(), Error> {
+    let svd = fs::read_to_string(input)?;
+    let source = generate(&svd)?;
+    fs::write(output, source)?;
+    return Ok(());
+}
@@ -0,0 +1,30 @@
+extern crate bitfield_register_svd;
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+fn run(args: &[String]) -> Result<(), bitfield_register_svd::Error> {
+    let svd = fs::read_to_string(&args[0])?;
+    let source = bitfield_register_svd::generate(&svd)?;
+
+    match args.get(1) {
+        Some(output) => fs::write(output, source)?,
+        None => io::stdout().write_all(source.as_bytes())?,
+    }
+    return Ok(());
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("usage: bitfield-register-svd <input.svd> [output.rs]");
+        process::exit(2);
+    }
+
+    if let Err(err) = run(&args) {
+        eprintln!("bitfield-register-svd: {}", err);
+        process::exit(1);
+    }
+}
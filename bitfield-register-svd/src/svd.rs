@@ -0,0 +1,343 @@
+// subset of CMSIS-SVD needed to describe registers: device, peripherals, registers, fields, enumerated values
+use roxmltree::Node;
+
+use super::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Access {
+    ReadWrite,
+    ReadOnly,
+    WriteOnly,
+}
+
+#[derive(Debug, Clone)]
+pub struct Device {
+    pub name: String,
+    pub description: Option<String>,
+    pub peripherals: Vec<Peripheral>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Peripheral {
+    pub name: String,
+    pub description: Option<String>,
+    pub base_address: u64,
+    pub registers: Vec<Register>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Register {
+    pub name: String,
+    // name without the dim placeholder, shared by all elements of a register array
+    pub base_name: String,
+    pub description: Option<String>,
+    pub address_offset: u64,
+    // in bits
+    pub size: u32,
+    pub access: Access,
+    pub reset_value: u64,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    pub bit_offset: u32,
+    pub bit_width: u32,
+    pub access: Access,
+    pub enumerated_values: Option<EnumeratedValues>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumeratedValues {
+    pub name: Option<String>,
+    pub values: Vec<EnumeratedValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumeratedValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub value: u64,
+}
+
+// size, access and reset value are inherited from device to peripheral to register
+#[derive(Debug, Clone, Copy)]
+struct RegisterProperties {
+    size: Option<u32>,
+    access: Option<Access>,
+    reset_value: Option<u64>,
+}
+
+impl RegisterProperties {
+    fn derive(&self, node: Node) -> Result<RegisterProperties, Error> {
+        let size = match child_text(node, "size") {
+            Some(size) => Some(parse_number(&size)? as u32),
+            None => self.size,
+        };
+        let access = match child_text(node, "access") {
+            Some(access) => Some(parse_access(&access)?),
+            None => self.access,
+        };
+        let reset_value = match child_text(node, "resetValue") {
+            Some(reset_value) => Some(parse_number(&reset_value)?),
+            None => self.reset_value,
+        };
+        return Ok(RegisterProperties { size, access, reset_value });
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    return node.children().find(|child| child.has_tag_name(name));
+}
+
+fn children<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    return node.children().filter(move |child| child.has_tag_name(name));
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    return child(node, name).and_then(|child| child.text()).map(|text| text.trim().to_string());
+}
+
+fn required_text(node: Node, name: &str) -> Result<String, Error> {
+    return child_text(node, name).ok_or_else(|| {
+        Error::Svd(format!("<{}> without <{}>", node.tag_name().name(), name))
+    });
+}
+
+// descriptions are often wrapped and indented in the xml, keep them on one line
+fn description(node: Node) -> Option<String> {
+    return child_text(node, "description")
+        .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|text| !text.is_empty());
+}
+
+// scaledNonNegativeInteger: decimal, 0x hex or #binary
+pub fn parse_number(text: &str) -> Result<u64, Error> {
+    let text = text.trim();
+    let result = if text.starts_with("0x") || text.starts_with("0X") {
+        u64::from_str_radix(&text[2..], 16)
+    } else if text.starts_with('#') {
+        u64::from_str_radix(&text[1..], 2)
+    } else {
+        text.parse::<u64>()
+    };
+    return result.map_err(|_| Error::Svd(format!("invalid number '{}'", text)));
+}
+
+fn parse_access(text: &str) -> Result<Access, Error> {
+    return match text {
+        "read-write" | "read-writeOnce" => Ok(Access::ReadWrite),
+        "read-only" => Ok(Access::ReadOnly),
+        "write-only" | "writeOnce" => Ok(Access::WriteOnly),
+        _ => Err(Error::Svd(format!("invalid access '{}'", text))),
+    };
+}
+
+pub fn parse_device(svd: &str) -> Result<Device, Error> {
+    let document = roxmltree::Document::parse(svd)?;
+    let device = document.root_element();
+    if !device.has_tag_name("device") {
+        return Err(Error::Svd(format!("expected <device>, found <{}>", device.tag_name().name())));
+    }
+
+    let defaults = RegisterProperties { size: None, access: None, reset_value: None };
+    let properties = defaults.derive(device)?;
+
+    let mut peripherals: Vec<Peripheral> = vec![];
+    if let Some(peripherals_node) = child(device, "peripherals") {
+        for node in children(peripherals_node, "peripheral") {
+            let peripheral = parse_peripheral(node, properties, &peripherals)?;
+            peripherals.push(peripheral);
+        }
+    }
+
+    return Ok(Device {
+        name: required_text(device, "name")?,
+        description: description(device),
+        peripherals,
+    });
+}
+
+fn parse_peripheral(node: Node, properties: RegisterProperties, parsed: &Vec<Peripheral>) -> Result<Peripheral, Error> {
+    let name = required_text(node, "name")?;
+    let base_address = parse_number(&required_text(node, "baseAddress")?)?;
+    let properties = properties.derive(node)?;
+
+    let mut registers = vec![];
+    if let Some(registers_node) = child(node, "registers") {
+        for register_node in registers_node.children().filter(|child| child.is_element()) {
+            if register_node.has_tag_name("cluster") {
+                return Err(Error::Unsupported(format!("cluster in peripheral {}", name)));
+            }
+            if register_node.has_tag_name("register") {
+                registers.extend(parse_register(register_node, properties)?);
+            }
+        }
+    }
+
+    let mut description = description(node);
+
+    if let Some(derived_from) = node.attribute("derivedFrom") {
+        let base = parsed.iter().find(|peripheral| peripheral.name == derived_from).ok_or_else(|| {
+            Error::Svd(format!("peripheral {} derived from unknown peripheral {}", name, derived_from))
+        })?;
+        if registers.is_empty() {
+            registers = base.registers.clone();
+        }
+        if description.is_none() {
+            description = base.description.clone();
+        }
+    }
+
+    return Ok(Peripheral { name, description, base_address, registers });
+}
+
+// dimIndex: "0-3" or "A,B,C", defaults to 0..dim
+fn dim_indices(node: Node, dim: u64) -> Result<Vec<String>, Error> {
+    let indices: Vec<String> = match child_text(node, "dimIndex") {
+        Some(text) => {
+            let range: Vec<&str> = text.split('-').collect();
+            if range.len() == 2 {
+                let from = parse_number(range[0])?;
+                let to = parse_number(range[1])?;
+                (from..to + 1).map(|i| i.to_string()).collect()
+            } else {
+                text.split(',').map(|index| index.trim().to_string()).collect()
+            }
+        }
+        None => (0..dim).map(|i| i.to_string()).collect(),
+    };
+
+    if indices.len() as u64 != dim {
+        return Err(Error::Svd(format!("dimIndex '{}' does not match dim {}", indices.join(","), dim)));
+    }
+    return Ok(indices);
+}
+
+fn parse_register(node: Node, properties: RegisterProperties) -> Result<Vec<Register>, Error> {
+    let name = required_text(node, "name")?;
+    let address_offset = parse_number(&required_text(node, "addressOffset")?)?;
+    let properties = properties.derive(node)?;
+
+    let size = properties.size.ok_or_else(|| Error::Svd(format!("register {} without size", name)))?;
+    if size == 0 || size % 8 != 0 {
+        return Err(Error::Unsupported(format!("register {} of {} bits", name, size)));
+    }
+    let access = properties.access.unwrap_or(Access::ReadWrite);
+
+    let mut fields = vec![];
+    if let Some(fields_node) = child(node, "fields") {
+        for field_node in children(fields_node, "field") {
+            fields.push(parse_field(field_node, access)?);
+        }
+    }
+
+    let register = Register {
+        name: name.clone(),
+        base_name: name.replace("[%s]", "").replace("%s", ""),
+        description: description(node),
+        address_offset,
+        size,
+        access,
+        reset_value: properties.reset_value.unwrap_or(0),
+        fields,
+    };
+
+    let dim = match child_text(node, "dim") {
+        Some(dim) => parse_number(&dim)?,
+        None => return Ok(vec![register]),
+    };
+    let dim_increment = parse_number(&required_text(node, "dimIncrement")?)?;
+
+    let mut registers = vec![];
+    for (i, index) in dim_indices(node, dim)?.iter().enumerate() {
+        let mut element = register.clone();
+        element.name = name.replace("[%s]", index).replace("%s", index);
+        element.address_offset = address_offset + i as u64 * dim_increment;
+        registers.push(element);
+    }
+    return Ok(registers);
+}
+
+// field position: bitOffset/bitWidth, lsb/msb or bitRange "[msb:lsb]"
+fn parse_field_position(node: Node, name: &str) -> Result<(u32, u32), Error> {
+    if let Some(bit_offset) = child_text(node, "bitOffset") {
+        let bit_offset = parse_number(&bit_offset)? as u32;
+        let bit_width = match child_text(node, "bitWidth") {
+            Some(bit_width) => parse_number(&bit_width)? as u32,
+            None => 1,
+        };
+        return Ok((bit_offset, bit_width));
+    }
+
+    if let (Some(lsb), Some(msb)) = (child_text(node, "lsb"), child_text(node, "msb")) {
+        let lsb = parse_number(&lsb)? as u32;
+        let msb = parse_number(&msb)? as u32;
+        if msb < lsb {
+            return Err(Error::Svd(format!("field {} with msb below lsb", name)));
+        }
+        return Ok((lsb, msb - lsb + 1));
+    }
+
+    if let Some(bit_range) = child_text(node, "bitRange") {
+        let range = bit_range.trim_start_matches('[').trim_end_matches(']');
+        let bounds: Vec<&str> = range.split(':').collect();
+        if bounds.len() == 2 {
+            let msb = parse_number(bounds[0])? as u32;
+            let lsb = parse_number(bounds[1])? as u32;
+            if msb >= lsb {
+                return Ok((lsb, msb - lsb + 1));
+            }
+        }
+        return Err(Error::Svd(format!("field {} with invalid bitRange '{}'", name, bit_range)));
+    }
+
+    return Err(Error::Svd(format!("field {} without position", name)));
+}
+
+fn parse_field(node: Node, register_access: Access) -> Result<Field, Error> {
+    let name = required_text(node, "name")?;
+    let (bit_offset, bit_width) = parse_field_position(node, &name)?;
+
+    let access = match child_text(node, "access") {
+        Some(access) => parse_access(&access)?,
+        None => register_access,
+    };
+
+    let enumerated_values = match child(node, "enumeratedValues") {
+        Some(values_node) => Some(parse_enumerated_values(values_node, &name)?),
+        None => None,
+    };
+
+    return Ok(Field {
+        description: description(node),
+        name,
+        bit_offset,
+        bit_width,
+        access,
+        enumerated_values,
+    });
+}
+
+fn parse_enumerated_values(node: Node, field_name: &str) -> Result<EnumeratedValues, Error> {
+    let mut values = vec![];
+    for value_node in children(node, "enumeratedValue") {
+        // isDefault values have no value of their own, they are covered by the fallback variant
+        let value = match child_text(value_node, "value") {
+            Some(value) => value,
+            None => continue,
+        };
+        if value.starts_with('#') && value.contains(|c| c == 'x' || c == 'X') {
+            return Err(Error::Unsupported(format!("don't care bits in enumerated value of field {}", field_name)));
+        }
+        values.push(EnumeratedValue {
+            name: required_text(value_node, "name")?,
+            description: description(value_node),
+            value: parse_number(&value)?,
+        });
+    }
+
+    return Ok(EnumeratedValues { name: child_text(node, "name"), values });
+}
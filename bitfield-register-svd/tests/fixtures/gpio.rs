@@ -0,0 +1,76 @@
+// Generated by bitfield-register-svd from GPIO_DEMO, do not edit.
+
+pub mod gpio {
+    use bitfield_register_macro::register;
+
+    /// Pin configuration
+    #[register(address = 0x50000010, size = 1, reset = 0x00)]
+    pub struct PinCfg0 {
+        /// Pin type
+        #[bitfield(from = 0, to = 1)]
+        type_: PinCfgType,
+        #[bitfield(at = 4)]
+        pull_up: bool,
+    }
+
+    /// Pin type
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PinCfgType {
+        Input,
+        Output,
+        Unknown(u8),
+    }
+
+    impl ::bitfield_register::FromBitfield<[u8;1]> for PinCfgType {
+        fn from_bitfield(array: [u8;1]) -> Self {
+            return match <u8 as ::bitfield_register::FromBitfield<[u8;1]>>::from_bitfield(array) {
+                0 => PinCfgType::Input,
+                1 => PinCfgType::Output,
+                value => PinCfgType::Unknown(value),
+            };
+        }
+    }
+
+    impl ::bitfield_register::IntoBitfield<[u8;1]> for PinCfgType {
+        fn into_bitfield(self) -> [u8;1] {
+            let value: u8 = match self {
+                PinCfgType::Input => 0,
+                PinCfgType::Output => 1,
+                PinCfgType::Unknown(value) => value,
+            };
+            return ::bitfield_register::IntoBitfield::into_bitfield(value);
+        }
+    }
+
+    /// Pin configuration
+    #[register(address = 0x50000011, size = 1, reset = 0x00)]
+    pub struct PinCfg1 {
+        /// Pin type
+        #[bitfield(from = 0, to = 1)]
+        type_: PinCfgType,
+        #[bitfield(at = 4)]
+        pull_up: bool,
+    }
+
+    /// Pin configuration
+    #[register(address = 0x50000012, size = 1, reset = 0x00)]
+    pub struct PinCfg2 {
+        /// Pin type
+        #[bitfield(from = 0, to = 1)]
+        type_: PinCfgType,
+        #[bitfield(at = 4)]
+        pull_up: bool,
+    }
+
+    #[register(address = 0x50000020, size = 2, reset = 0x00FF)]
+    pub struct Porta {
+        #[bitfield(from = 0, to = 15)]
+        value: u16,
+    }
+
+    #[register(address = 0x50000022, size = 2, reset = 0x00FF)]
+    pub struct Portb {
+        #[bitfield(from = 0, to = 15)]
+        value: u16,
+    }
+}
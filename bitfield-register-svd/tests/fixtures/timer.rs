@@ -0,0 +1,200 @@
+// Generated by bitfield-register-svd from TIMER_DEMO, do not edit.
+// Timer peripherals of a demo device
+
+/// General purpose timer
+pub mod timer0 {
+    use bitfield_register_macro::register;
+
+    /// Control register
+    #[register(address = 0x40000000, size = 4, reset = 0x00000300)]
+    pub struct Ctrl {
+        /// Enable the timer
+        #[bitfield(at = 0)]
+        en: bool,
+        /// Counting mode
+        #[bitfield(from = 1, to = 2)]
+        mode: CtrlMode,
+        /// Clock divider, power of two
+        #[bitfield(from = 8, to = 11)]
+        prescaler: u8,
+        /// Current counter value
+        #[bitfield(from = 16, to = 31, access = "read-only")]
+        count: u16,
+    }
+
+    /// Counting mode
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CtrlMode {
+        /// Stop at the end of the period
+        OneShot,
+        /// Reload at the end of the period
+        Periodic,
+        Continuous,
+        Unknown(u8),
+    }
+
+    impl ::bitfield_register::FromBitfield<[u8;1]> for CtrlMode {
+        fn from_bitfield(array: [u8;1]) -> Self {
+            return match <u8 as ::bitfield_register::FromBitfield<[u8;1]>>::from_bitfield(array) {
+                0 => CtrlMode::OneShot,
+                1 => CtrlMode::Periodic,
+                2 => CtrlMode::Continuous,
+                value => CtrlMode::Unknown(value),
+            };
+        }
+    }
+
+    impl ::bitfield_register::IntoBitfield<[u8;1]> for CtrlMode {
+        fn into_bitfield(self) -> [u8;1] {
+            let value: u8 = match self {
+                CtrlMode::OneShot => 0,
+                CtrlMode::Periodic => 1,
+                CtrlMode::Continuous => 2,
+                CtrlMode::Unknown(value) => value,
+            };
+            return ::bitfield_register::IntoBitfield::into_bitfield(value);
+        }
+    }
+
+    /// Status register
+    #[register(address = 0x40000004, size = 4, reset = 0x00000000)]
+    pub struct Status {
+        /// Counter overflow
+        #[bitfield(at = 0, access = "read-only")]
+        ovf: bool,
+        #[bitfield(at = 1, access = "read-only")]
+        dir: Direction,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Direction {
+        Up,
+        Down,
+    }
+
+    impl ::bitfield_register::FromBitfield<[u8;1]> for Direction {
+        fn from_bitfield(array: [u8;1]) -> Self {
+            return match <u8 as ::bitfield_register::FromBitfield<[u8;1]>>::from_bitfield(array) {
+                0 => Direction::Up,
+                1 => Direction::Down,
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    impl ::bitfield_register::IntoBitfield<[u8;1]> for Direction {
+        fn into_bitfield(self) -> [u8;1] {
+            let value: u8 = match self {
+                Direction::Up => 0,
+                Direction::Down => 1,
+            };
+            return ::bitfield_register::IntoBitfield::into_bitfield(value);
+        }
+    }
+
+    /// Reload value
+    #[register(address = 0x40000008, size = 4, reset = 0x0000FFFF)]
+    pub struct Load {
+        #[bitfield(from = 0, to = 31, access = "write-only")]
+        value: u32,
+    }
+}
+
+/// General purpose timer
+pub mod timer1 {
+    use bitfield_register_macro::register;
+
+    /// Control register
+    #[register(address = 0x40001000, size = 4, reset = 0x00000300)]
+    pub struct Ctrl {
+        /// Enable the timer
+        #[bitfield(at = 0)]
+        en: bool,
+        /// Counting mode
+        #[bitfield(from = 1, to = 2)]
+        mode: CtrlMode,
+        /// Clock divider, power of two
+        #[bitfield(from = 8, to = 11)]
+        prescaler: u8,
+        /// Current counter value
+        #[bitfield(from = 16, to = 31, access = "read-only")]
+        count: u16,
+    }
+
+    /// Counting mode
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CtrlMode {
+        /// Stop at the end of the period
+        OneShot,
+        /// Reload at the end of the period
+        Periodic,
+        Continuous,
+        Unknown(u8),
+    }
+
+    impl ::bitfield_register::FromBitfield<[u8;1]> for CtrlMode {
+        fn from_bitfield(array: [u8;1]) -> Self {
+            return match <u8 as ::bitfield_register::FromBitfield<[u8;1]>>::from_bitfield(array) {
+                0 => CtrlMode::OneShot,
+                1 => CtrlMode::Periodic,
+                2 => CtrlMode::Continuous,
+                value => CtrlMode::Unknown(value),
+            };
+        }
+    }
+
+    impl ::bitfield_register::IntoBitfield<[u8;1]> for CtrlMode {
+        fn into_bitfield(self) -> [u8;1] {
+            let value: u8 = match self {
+                CtrlMode::OneShot => 0,
+                CtrlMode::Periodic => 1,
+                CtrlMode::Continuous => 2,
+                CtrlMode::Unknown(value) => value,
+            };
+            return ::bitfield_register::IntoBitfield::into_bitfield(value);
+        }
+    }
+
+    /// Status register
+    #[register(address = 0x40001004, size = 4, reset = 0x00000000)]
+    pub struct Status {
+        /// Counter overflow
+        #[bitfield(at = 0, access = "read-only")]
+        ovf: bool,
+        #[bitfield(at = 1, access = "read-only")]
+        dir: Direction,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Direction {
+        Up,
+        Down,
+    }
+
+    impl ::bitfield_register::FromBitfield<[u8;1]> for Direction {
+        fn from_bitfield(array: [u8;1]) -> Self {
+            return match <u8 as ::bitfield_register::FromBitfield<[u8;1]>>::from_bitfield(array) {
+                0 => Direction::Up,
+                1 => Direction::Down,
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    impl ::bitfield_register::IntoBitfield<[u8;1]> for Direction {
+        fn into_bitfield(self) -> [u8;1] {
+            let value: u8 = match self {
+                Direction::Up => 0,
+                Direction::Down => 1,
+            };
+            return ::bitfield_register::IntoBitfield::into_bitfield(value);
+        }
+    }
+
+    /// Reload value
+    #[register(address = 0x40001008, size = 4, reset = 0x0000FFFF)]
+    pub struct Load {
+        #[bitfield(from = 0, to = 31, access = "write-only")]
+        value: u32,
+    }
+}
@@ -0,0 +1,62 @@
+// golden sources have to compile with the register macro and behave as described by the svd
+extern crate bitfield_register;
+extern crate bitfield_register_macro;
+
+mod timer {
+    include!("fixtures/timer.rs");
+}
+
+mod gpio {
+    include!("fixtures/gpio.rs");
+}
+
+use bitfield_register::{AddressedRegister, BitfieldRegister};
+
+#[test]
+fn timer_test() {
+    use timer::timer0::{Ctrl, CtrlMode, Direction, Load, Status};
+
+    assert_eq!(Ctrl::ADDRESS, 0x40000000);
+    assert_eq!(Status::ADDRESS, 0x40000004);
+    assert_eq!(timer::timer1::Ctrl::ADDRESS, 0x40001000);
+    assert_eq!(Ctrl::REGISTER_SIZE, 4);
+
+    let mut ctrl = Ctrl::default();
+    assert_eq!(ctrl.data(), &[0x00, 0x03, 0x00, 0x00]);
+    assert_eq!(ctrl.get_prescaler(), 3);
+    assert_eq!(ctrl.get_mode(), CtrlMode::OneShot);
+
+    ctrl.set_mode(CtrlMode::Continuous);
+    ctrl.set_en(true);
+    assert_eq!(ctrl.data(), &[0b101, 0x03, 0x00, 0x00]);
+
+    let ctrl = Ctrl::from([0b110, 0x00, 0x34, 0x12]);
+    assert_eq!(ctrl.get_mode(), CtrlMode::Unknown(3));
+    assert_eq!(ctrl.get_count(), 0x1234);
+
+    let status = Status::from([0b10, 0, 0, 0]);
+    assert_eq!(status.get_ovf(), false);
+    assert_eq!(status.get_dir(), Direction::Down);
+
+    let mut load = Load::default();
+    assert_eq!(load.data(), &[0xFF, 0xFF, 0x00, 0x00]);
+    load.set_value(0x12345678);
+    assert_eq!(load.data(), &[0x78, 0x56, 0x34, 0x12]);
+}
+
+#[test]
+fn gpio_test() {
+    use gpio::gpio::{PinCfg0, PinCfg2, PinCfgType, Porta, Portb};
+
+    assert_eq!(PinCfg0::ADDRESS, 0x50000010);
+    assert_eq!(PinCfg2::ADDRESS, 0x50000012);
+    assert_eq!(Porta::ADDRESS, 0x50000020);
+    assert_eq!(Portb::ADDRESS, 0x50000022);
+
+    let mut pin = PinCfg2::default();
+    pin.set_type_(PinCfgType::Output);
+    pin.set_pull_up(true);
+    assert_eq!(pin.data(), &[0b10001]);
+
+    assert_eq!(Portb::default().get_value(), 0xFF);
+}
@@ -0,0 +1,76 @@
+extern crate bitfield_register_svd;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use bitfield_register_svd::{generate, Error};
+
+fn fixture(name: &str) -> PathBuf {
+    return PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(name);
+}
+
+// compares generated source with fixtures/<name>.rs, UPDATE_GOLDEN=1 rewrites the expected files
+fn check_golden(name: &str) {
+    let svd = fs::read_to_string(fixture(&format!("{}.svd", name))).unwrap();
+    let generated = generate(&svd).unwrap();
+
+    let golden = fixture(&format!("{}.rs", name));
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&golden, &generated).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&golden).unwrap();
+    assert_eq!(generated, expected, "generated source differs from {}", golden.display());
+}
+
+#[test]
+fn timer_test() {
+    check_golden("timer");
+}
+
+#[test]
+fn gpio_test() {
+    check_golden("gpio");
+}
+
+fn device(registers: &str) -> String {
+    return format!(
+        "<device><name>D</name><size>32</size><peripherals><peripheral>\
+         <name>P</name><baseAddress>0</baseAddress><registers>{}</registers>\
+         </peripheral></peripherals></device>",
+        registers
+    );
+}
+
+#[test]
+fn error_test() {
+    match generate("<device>") {
+        Err(Error::Xml(_)) => {}
+        result => panic!("unexpected {:?}", result),
+    }
+
+    match generate("<peripheral/>") {
+        Err(Error::Svd(_)) => {}
+        result => panic!("unexpected {:?}", result),
+    }
+
+    match generate(&device("<cluster><name>C</name></cluster>")) {
+        Err(Error::Unsupported(_)) => {}
+        result => panic!("unexpected {:?}", result),
+    }
+
+    match generate(&device("<register><name>R</name><addressOffset>0xZZ</addressOffset></register>")) {
+        Err(Error::Svd(message)) => assert_eq!(message, "invalid number '0xZZ'"),
+        result => panic!("unexpected {:?}", result),
+    }
+
+    let field_outside = "<register><name>R</name><addressOffset>0</addressOffset><fields>\
+                         <field><name>F</name><bitOffset>30</bitOffset><bitWidth>4</bitWidth></field>\
+                         </fields></register>";
+    match generate(&device(field_outside)) {
+        Err(Error::Svd(message)) => assert_eq!(message, "field R.F does not fit register"),
+        result => panic!("unexpected {:?}", result),
+    }
+}
@@ -0,0 +1,48 @@
+use super::{AddressedRegister, MockError, MockInterface, RegisterInterface};
+
+// async counterpart of RegisterInterface, for drivers running on an async executor
+#[allow(async_fn_in_trait)]
+pub trait AsyncRegisterInterface {
+    type Error;
+
+    async fn read_bytes(&mut self, address: u32, data: &mut [u8]) -> Result<(), Self::Error>;
+    async fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), Self::Error>;
+
+    async fn read_register<R>(&mut self) -> Result<R, Self::Error>
+        where R: AddressedRegister + Default,
+              R::Data: AsMut<[u8]>
+    {
+        let mut register = R::default();
+        self.read_bytes(R::ADDRESS, register.data_mut().as_mut()).await?;
+        return Ok(register);
+    }
+
+    async fn write_register<R>(&mut self, register: &R) -> Result<(), Self::Error>
+        where R: AddressedRegister
+    {
+        return self.write_bytes(R::ADDRESS, register.as_bytes()).await;
+    }
+
+    // read-modify-write
+    async fn modify_register<R, F>(&mut self, modify: F) -> Result<(), Self::Error>
+        where R: AddressedRegister + Default,
+              R::Data: AsMut<[u8]>,
+              F: FnOnce(&mut R)
+    {
+        let mut register: R = self.read_register().await?;
+        modify(&mut register);
+        return self.write_register(&register).await;
+    }
+}
+
+impl<const SIZE: usize> AsyncRegisterInterface for MockInterface<SIZE> {
+    type Error = MockError;
+
+    async fn read_bytes(&mut self, address: u32, data: &mut [u8]) -> Result<(), MockError> {
+        return RegisterInterface::read_bytes(self, address, data);
+    }
+
+    async fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), MockError> {
+        return RegisterInterface::write_bytes(self, address, data);
+    }
+}
@@ -0,0 +1,34 @@
+// checksums maintained by #[checksum(kind = ..., over = ..., at = ...)]
+
+// true when bits first_bit..=last_bit of bytes hold an odd number of ones, byte 0 holds bits 0..8
+pub fn parity(bytes: &[u8], first_bit: usize, last_bit: usize) -> bool {
+    let mut odd = false;
+    for bit in first_bit..=last_bit {
+        odd ^= bytes[bit / 8] & (1 << (bit % 8)) != 0;
+    }
+    return odd;
+}
+
+// CRC-8/SMBUS: polynomial 0x07, initial value 0x00, no reflection, no final xor
+pub fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc: u8 = 0;
+    for &byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { crc << 1 ^ 0x07 } else { crc << 1 };
+        }
+    }
+    return crc;
+}
+
+// CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF, no reflection, no final xor
+pub fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { crc << 1 ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    return crc;
+}
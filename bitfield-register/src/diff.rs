@@ -0,0 +1,65 @@
+use core::fmt;
+
+use super::FieldDescription;
+
+// raw value of a field that differs between two register values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldChange {
+    pub name: &'static str,
+    pub old: u128,
+    pub new: u128,
+    pub bit_width: usize,
+}
+
+// mode: 0b01 -> 0b11
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = usize::min(self.bit_width, 128);
+        return write!(f, "{}: 0b{:0width$b} -> 0b{:0width$b}", self.name, self.old, self.new, width = width);
+    }
+}
+
+// bits [first_bit, first_bit + bit_width) of bytes, byte 0 holds bits 0..8
+// fields wider than 128 bits are compared and reported by their low 128 bits
+pub fn field_value(bytes: &[u8], first_bit: usize, bit_width: usize) -> u128 {
+    let mut value: u128 = 0;
+    for i in 0..usize::min(bit_width, 128) {
+        let bit = first_bit + i;
+        if bytes[bit / 8] & (1 << (bit % 8)) != 0 {
+            value |= 1 << i;
+        }
+    }
+    return value;
+}
+
+// changed fields of two register values, in field order, returned by the generated diff()
+// aliases are skipped, their bits are reported by the fields owning them
+pub struct FieldDiff<'a> {
+    fields: ::core::slice::Iter<'static, FieldDescription>,
+    old: &'a [u8],
+    new: &'a [u8],
+}
+
+impl<'a> FieldDiff<'a> {
+    pub fn new(fields: &'static [FieldDescription], old: &'a [u8], new: &'a [u8]) -> Self {
+        return FieldDiff { fields: fields.iter(), old, new };
+    }
+}
+
+impl<'a> Iterator for FieldDiff<'a> {
+    type Item = FieldChange;
+
+    fn next(&mut self) -> Option<FieldChange> {
+        for field in &mut self.fields {
+            if field.alias {
+                continue;
+            }
+            let old = field.value(self.old);
+            let new = field.value(self.new);
+            if old != new {
+                return Some(FieldChange { name: field.name, old, new, bit_width: field.bit_width() });
+            }
+        }
+        return None;
+    }
+}
@@ -0,0 +1,141 @@
+use core::fmt;
+
+use super::{FromBitfield, IntoBitfield};
+
+// bcd value that is not a number of the field width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BcdError {
+    // a digit above 9, e.g. read from a device
+    InvalidDigit,
+    // more decimal digits than the field holds
+    Overflow,
+}
+
+impl fmt::Display for BcdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            &BcdError::InvalidDigit => write!(f, "invalid bcd digit"),
+            &BcdError::Overflow => write!(f, "value has too many decimal digits"),
+        };
+    }
+}
+
+// binary-coded decimal field, one decimal digit per nibble, e.g. time and date of RTC chips
+// holds the raw bits so values with invalid digits survive a read, decode() checks them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bcd<T>(T);
+
+// Gray code field, consecutive values differ in one bit, e.g. positions of rotary encoders
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gray<T>(T);
+
+// unsigned integers holding the raw bits of Bcd and Gray
+pub trait RawInteger: Copy {
+    const BITS: usize;
+    fn to_u64(self) -> u64;
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_raw_integer {
+    ($($ty:ty),*) => ($(
+        impl RawInteger for $ty {
+            const BITS: usize = 8 * ::core::mem::size_of::<$ty>();
+            fn to_u64(self) -> u64 {
+                return self as u64;
+            }
+            fn from_u64(value: u64) -> Self {
+                return value as $ty;
+            }
+        }
+    )*)
+}
+
+impl_raw_integer!(u8, u16, u32);
+
+impl<T: RawInteger> Bcd<T> {
+    pub fn from_raw(raw: T) -> Self {
+        return Bcd(raw);
+    }
+
+    pub fn raw(self) -> T {
+        return self.0;
+    }
+
+    pub fn encode(value: T) -> Result<Self, BcdError> {
+        let mut value = value.to_u64();
+        let mut raw: u64 = 0;
+        let mut shift = 0;
+        while value != 0 {
+            if shift >= T::BITS {
+                return Err(BcdError::Overflow);
+            }
+            raw |= (value % 10) << shift;
+            value /= 10;
+            shift += 4;
+        }
+        return Ok(Bcd(T::from_u64(raw)));
+    }
+
+    pub fn decode(self) -> Result<T, BcdError> {
+        let raw = self.0.to_u64();
+        let mut value: u64 = 0;
+        for i in (0..T::BITS / 4).rev() {
+            let digit = raw >> (4 * i) & 0xF;
+            if digit > 9 {
+                return Err(BcdError::InvalidDigit);
+            }
+            value = value * 10 + digit;
+        }
+        return Ok(T::from_u64(value));
+    }
+}
+
+impl<T: RawInteger> Gray<T> {
+    pub fn from_raw(raw: T) -> Self {
+        return Gray(raw);
+    }
+
+    pub fn raw(self) -> T {
+        return self.0;
+    }
+
+    pub fn encode(value: T) -> Self {
+        let value = value.to_u64();
+        return Gray(T::from_u64(value ^ value >> 1));
+    }
+
+    pub fn decode(self) -> T {
+        let mut value = self.0.to_u64();
+        let mut shift = 1;
+        while shift < T::BITS {
+            value ^= value >> shift;
+            shift <<= 1;
+        }
+        return T::from_u64(value);
+    }
+}
+
+// fields store the raw bits, any width the raw type supports
+impl<A, T: FromBitfield<A>> FromBitfield<A> for Bcd<T> {
+    fn from_bitfield(array: A) -> Self {
+        return Bcd(T::from_bitfield(array));
+    }
+}
+
+impl<A, T: IntoBitfield<A>> IntoBitfield<A> for Bcd<T> {
+    fn into_bitfield(self) -> A {
+        return self.0.into_bitfield();
+    }
+}
+
+impl<A, T: FromBitfield<A>> FromBitfield<A> for Gray<T> {
+    fn from_bitfield(array: A) -> Self {
+        return Gray(T::from_bitfield(array));
+    }
+}
+
+impl<A, T: IntoBitfield<A>> IntoBitfield<A> for Gray<T> {
+    fn into_bitfield(self) -> A {
+        return self.0.into_bitfield();
+    }
+}
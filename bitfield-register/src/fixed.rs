@@ -0,0 +1,211 @@
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::{FromBitfield, IntoBitfield};
+
+// signed fixed-point field, two's complement over INT + FRAC bits (at most 32)
+// INT counts the sign bit, Q4.12 is Fixed<4, 12>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<const INT: u32, const FRAC: u32>(i32);
+
+// unsigned fixed-point field over INT + FRAC bits (at most 32), UQ8.8 is UFixed<8, 8>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UFixed<const INT: u32, const FRAC: u32>(u32);
+
+impl<const INT: u32, const FRAC: u32> Fixed<INT, FRAC> {
+    pub const BITS: u32 = {
+        assert!(INT + FRAC > 0 && INT + FRAC <= 32, "fixed-point value should have 1 to 32 bits");
+        INT + FRAC
+    };
+    pub const MIN: Self = Fixed(Self::wrap(-1 << (Self::BITS - 1)));
+    pub const MAX: Self = Fixed(Self::wrap((1 << (Self::BITS - 1)) - 1));
+
+    // raw is sign-extended from INT + FRAC bits, higher bits are ignored
+    pub const fn from_raw(raw: i32) -> Self {
+        return Fixed(Self::wrap(raw as i64));
+    }
+
+    pub const fn raw(self) -> i32 {
+        return self.0;
+    }
+
+    // keeps the low BITS bits of raw, sign-extended
+    const fn wrap(raw: i64) -> i32 {
+        let shift = 64 - Self::BITS;
+        return ((raw << shift) >> shift) as i32;
+    }
+
+    // rounds to the nearest value, saturates at MIN and MAX
+    pub fn from_f64(value: f64) -> Self {
+        let scaled = value * (1u64 << FRAC) as f64;
+        let rounded = if scaled >= 0.0 { scaled + 0.5 } else { scaled - 0.5 };
+        if rounded >= Self::MAX.0 as f64 {
+            return Self::MAX;
+        }
+        if rounded <= Self::MIN.0 as f64 {
+            return Self::MIN;
+        }
+        return Fixed(rounded as i32);
+    }
+
+    pub fn to_f64(self) -> f64 {
+        return self.0 as f64 / (1u64 << FRAC) as f64;
+    }
+
+    pub fn from_f32(value: f32) -> Self {
+        return Self::from_f64(value as f64);
+    }
+
+    pub fn to_f32(self) -> f32 {
+        return self.to_f64() as f32;
+    }
+}
+
+impl<const INT: u32, const FRAC: u32> UFixed<INT, FRAC> {
+    pub const BITS: u32 = {
+        assert!(INT + FRAC > 0 && INT + FRAC <= 32, "fixed-point value should have 1 to 32 bits");
+        INT + FRAC
+    };
+    pub const MIN: Self = UFixed(0);
+    pub const MAX: Self = UFixed(Self::wrap(u64::max_value()));
+
+    // higher bits than INT + FRAC are ignored
+    pub const fn from_raw(raw: u32) -> Self {
+        return UFixed(Self::wrap(raw as u64));
+    }
+
+    pub const fn raw(self) -> u32 {
+        return self.0;
+    }
+
+    const fn wrap(raw: u64) -> u32 {
+        return (raw & (u64::max_value() >> (64 - Self::BITS))) as u32;
+    }
+
+    // rounds to the nearest value, saturates at MIN and MAX
+    pub fn from_f64(value: f64) -> Self {
+        let rounded = value * (1u64 << FRAC) as f64 + 0.5;
+        if rounded >= Self::MAX.0 as f64 {
+            return Self::MAX;
+        }
+        if rounded <= 0.0 {
+            return Self::MIN;
+        }
+        return UFixed(rounded as u32);
+    }
+
+    pub fn to_f64(self) -> f64 {
+        return self.0 as f64 / (1u64 << FRAC) as f64;
+    }
+
+    pub fn from_f32(value: f32) -> Self {
+        return Self::from_f64(value as f64);
+    }
+
+    pub fn to_f32(self) -> f32 {
+        return self.to_f64() as f32;
+    }
+}
+
+// arithmetic wraps around INT + FRAC bits like the hardware does, division by zero panics
+macro_rules! impl_fixed_ops {
+    ($name:ident, $wide:ty) => (
+        impl<const INT: u32, const FRAC: u32> Add for $name<INT, FRAC> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                return $name(Self::wrap(self.0 as $wide + other.0 as $wide));
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> Sub for $name<INT, FRAC> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                return $name(Self::wrap((self.0 as $wide).wrapping_sub(other.0 as $wide)));
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> Mul for $name<INT, FRAC> {
+            type Output = Self;
+            fn mul(self, other: Self) -> Self {
+                return $name(Self::wrap((self.0 as $wide * other.0 as $wide) >> FRAC));
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> Div for $name<INT, FRAC> {
+            type Output = Self;
+            fn div(self, other: Self) -> Self {
+                return $name(Self::wrap(((self.0 as $wide) << FRAC) / other.0 as $wide));
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> From<$name<INT, FRAC>> for f32 {
+            fn from(value: $name<INT, FRAC>) -> f32 {
+                return value.to_f32();
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> From<$name<INT, FRAC>> for f64 {
+            fn from(value: $name<INT, FRAC>) -> f64 {
+                return value.to_f64();
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> fmt::Display for $name<INT, FRAC> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                return fmt::Display::fmt(&self.to_f64(), f);
+            }
+        }
+    )
+}
+
+impl_fixed_ops!(Fixed, i64);
+impl_fixed_ops!(UFixed, u64);
+
+impl<const INT: u32, const FRAC: u32> Neg for Fixed<INT, FRAC> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Fixed(Self::wrap(-(self.0 as i64)));
+    }
+}
+
+// fields of 1 to 4 bytes, the value is stored in its low INT + FRAC bits
+macro_rules! impl_fixed_bitfield {
+    ($($len:expr),*) => ($(
+        impl<const INT: u32, const FRAC: u32> FromBitfield<[u8;$len]> for Fixed<INT, FRAC> {
+            fn from_bitfield(array: [u8;$len]) -> Self {
+                let mut bytes = [0u8;4];
+                bytes[..$len].copy_from_slice(&array);
+                return Fixed::from_raw(i32::from_le_bytes(bytes));
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> IntoBitfield<[u8;$len]> for Fixed<INT, FRAC> {
+            fn into_bitfield(self) -> [u8;$len] {
+                // no sign bits above the value, a value wider than the field is an overflow
+                let bytes = UFixed::<INT, FRAC>::from_raw(self.0 as u32).0.to_le_bytes();
+                let mut array = [0u8;$len];
+                array.copy_from_slice(&bytes[..$len]);
+                return array;
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> FromBitfield<[u8;$len]> for UFixed<INT, FRAC> {
+            fn from_bitfield(array: [u8;$len]) -> Self {
+                let mut bytes = [0u8;4];
+                bytes[..$len].copy_from_slice(&array);
+                return UFixed::from_raw(u32::from_le_bytes(bytes));
+            }
+        }
+
+        impl<const INT: u32, const FRAC: u32> IntoBitfield<[u8;$len]> for UFixed<INT, FRAC> {
+            fn into_bitfield(self) -> [u8;$len] {
+                let bytes = self.0.to_le_bytes();
+                let mut array = [0u8;$len];
+                array.copy_from_slice(&bytes[..$len]);
+                return array;
+            }
+        }
+    )*)
+}
+
+impl_fixed_bitfield!(1, 2, 3, 4);
@@ -0,0 +1,172 @@
+use embedded_hal::i2c::{self, I2c};
+use embedded_hal::spi::{self, SpiDevice};
+
+use super::RegisterInterface;
+
+// register address size on the wire, multi-byte addresses are sent MSB first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressWidth {
+    U8,
+    U16,
+}
+
+impl AddressWidth {
+    fn encode(&self, address: u32) -> Option<([u8; 2], usize)> {
+        return match self {
+            &AddressWidth::U8 if address <= 0xFF => Some(([address as u8, 0], 1)),
+            &AddressWidth::U16 if address <= 0xFFFF => Some(([(address >> 8) as u8, address as u8], 2)),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    // register address with flags does not fit AddressWidth
+    Address(u32),
+    Bus(E),
+}
+
+// auto_increment is or'ed into the register address when more than one byte is transferred,
+// 0 for devices that always increment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct I2cConfig {
+    pub address_width: AddressWidth,
+    pub auto_increment: u32,
+}
+
+impl Default for I2cConfig {
+    fn default() -> Self {
+        return I2cConfig { address_width: AddressWidth::U8, auto_increment: 0 };
+    }
+}
+
+// read_flag and write_flag are or'ed into the register address to select the transfer direction,
+// auto_increment is or'ed in when more than one byte is transferred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpiConfig {
+    pub address_width: AddressWidth,
+    pub read_flag: u32,
+    pub write_flag: u32,
+    pub auto_increment: u32,
+}
+
+impl SpiConfig {
+    // MSB of the address byte set for reads, the most common convention
+    pub fn read_high() -> Self {
+        return SpiConfig { address_width: AddressWidth::U8, read_flag: 0x80, write_flag: 0, auto_increment: 0 };
+    }
+
+    // MSB of the address byte set for writes
+    pub fn write_high() -> Self {
+        return SpiConfig { address_width: AddressWidth::U8, read_flag: 0, write_flag: 0x80, auto_increment: 0 };
+    }
+}
+
+impl Default for SpiConfig {
+    fn default() -> Self {
+        return SpiConfig::read_high();
+    }
+}
+
+fn flagged_address(address: u32, flag: u32, auto_increment: u32, len: usize) -> u32 {
+    if len > 1 {
+        return address | flag | auto_increment;
+    }
+    return address | flag;
+}
+
+// RegisterInterface over an embedded-hal I2C bus, register address is written before the data
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    device_address: u8,
+    config: I2cConfig,
+}
+
+impl<I2C: I2c> I2cInterface<I2C> {
+    pub fn new(i2c: I2C, device_address: u8) -> Self {
+        return I2cInterface::with_config(i2c, device_address, Default::default());
+    }
+
+    pub fn with_config(i2c: I2C, device_address: u8, config: I2cConfig) -> Self {
+        return I2cInterface { i2c, device_address, config };
+    }
+
+    pub fn release(self) -> I2C {
+        return self.i2c;
+    }
+}
+
+impl<I2C: I2c> RegisterInterface for I2cInterface<I2C> {
+    type Error = Error<I2C::Error>;
+
+    fn read_bytes(&mut self, address: u32, data: &mut [u8]) -> Result<(), Self::Error> {
+        let address = flagged_address(address, 0, self.config.auto_increment, data.len());
+        let (address_bytes, address_len) = self.config.address_width.encode(address).ok_or(Error::Address(address))?;
+
+        return self.i2c
+            .write_read(self.device_address, &address_bytes[..address_len], data)
+            .map_err(Error::Bus);
+    }
+
+    fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let address = flagged_address(address, 0, self.config.auto_increment, data.len());
+        let (address_bytes, address_len) = self.config.address_width.encode(address).ok_or(Error::Address(address))?;
+
+        // consecutive writes of one transaction are sent without a restart
+        return self.i2c
+            .transaction(self.device_address, &mut [
+                i2c::Operation::Write(&address_bytes[..address_len]),
+                i2c::Operation::Write(data),
+            ])
+            .map_err(Error::Bus);
+    }
+}
+
+// RegisterInterface over an embedded-hal SPI device, register address is sent before the data
+pub struct SpiInterface<SPI> {
+    spi: SPI,
+    config: SpiConfig,
+}
+
+impl<SPI: SpiDevice> SpiInterface<SPI> {
+    pub fn new(spi: SPI) -> Self {
+        return SpiInterface::with_config(spi, Default::default());
+    }
+
+    pub fn with_config(spi: SPI, config: SpiConfig) -> Self {
+        return SpiInterface { spi, config };
+    }
+
+    pub fn release(self) -> SPI {
+        return self.spi;
+    }
+}
+
+impl<SPI: SpiDevice> RegisterInterface for SpiInterface<SPI> {
+    type Error = Error<SPI::Error>;
+
+    fn read_bytes(&mut self, address: u32, data: &mut [u8]) -> Result<(), Self::Error> {
+        let address = flagged_address(address, self.config.read_flag, self.config.auto_increment, data.len());
+        let (address_bytes, address_len) = self.config.address_width.encode(address).ok_or(Error::Address(address))?;
+
+        return self.spi
+            .transaction(&mut [
+                spi::Operation::Write(&address_bytes[..address_len]),
+                spi::Operation::Read(data),
+            ])
+            .map_err(Error::Bus);
+    }
+
+    fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let address = flagged_address(address, self.config.write_flag, self.config.auto_increment, data.len());
+        let (address_bytes, address_len) = self.config.address_width.encode(address).ok_or(Error::Address(address))?;
+
+        return self.spi
+            .transaction(&mut [
+                spi::Operation::Write(&address_bytes[..address_len]),
+                spi::Operation::Write(data),
+            ])
+            .map_err(Error::Bus);
+    }
+}
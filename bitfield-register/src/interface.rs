@@ -0,0 +1,100 @@
+use super::BitfieldRegister;
+
+// register with a fixed address on its device, set with #[register(address = x)]
+pub trait AddressedRegister: BitfieldRegister {
+    const ADDRESS: u32;
+}
+
+// byte-level access to a register-mapped device, usually behind I2C or SPI
+// address is the address of the first register byte, data length is the amount of bytes to transfer
+pub trait RegisterInterface {
+    type Error;
+
+    fn read_bytes(&mut self, address: u32, data: &mut [u8]) -> Result<(), Self::Error>;
+    fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), Self::Error>;
+
+    fn read_register<R>(&mut self) -> Result<R, Self::Error>
+        where R: AddressedRegister + Default,
+              R::Data: AsMut<[u8]>
+    {
+        let mut register = R::default();
+        self.read_bytes(R::ADDRESS, register.data_mut().as_mut())?;
+        return Ok(register);
+    }
+
+    fn write_register<R>(&mut self, register: &R) -> Result<(), Self::Error>
+        where R: AddressedRegister
+    {
+        return self.write_bytes(R::ADDRESS, register.as_bytes());
+    }
+
+    // read-modify-write
+    fn modify_register<R, F>(&mut self, modify: F) -> Result<(), Self::Error>
+        where R: AddressedRegister + Default,
+              R::Data: AsMut<[u8]>,
+              F: FnOnce(&mut R)
+    {
+        let mut register: R = self.read_register()?;
+        modify(&mut register);
+        return self.write_register(&register);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockError {
+    OutOfBounds,
+}
+
+// in-memory device for host tests, address is an offset into memory
+pub struct MockInterface<const SIZE: usize> {
+    memory: [u8; SIZE],
+}
+
+impl<const SIZE: usize> MockInterface<SIZE> {
+    pub fn new() -> Self {
+        return MockInterface { memory: [0; SIZE] };
+    }
+
+    pub fn from_memory(memory: [u8; SIZE]) -> Self {
+        return MockInterface { memory };
+    }
+
+    pub fn memory(&self) -> &[u8; SIZE] {
+        return &self.memory;
+    }
+
+    pub fn memory_mut(&mut self) -> &mut [u8; SIZE] {
+        return &mut self.memory;
+    }
+
+    fn range(address: u32, len: usize) -> Result<::core::ops::Range<usize>, MockError> {
+        let from = address as usize;
+        let to = from.checked_add(len).ok_or(MockError::OutOfBounds)?;
+        if to > SIZE {
+            return Err(MockError::OutOfBounds);
+        }
+        return Ok(from..to);
+    }
+}
+
+impl<const SIZE: usize> Default for MockInterface<SIZE> {
+    fn default() -> Self {
+        return MockInterface::new();
+    }
+}
+
+impl<const SIZE: usize> RegisterInterface for MockInterface<SIZE> {
+    type Error = MockError;
+
+    fn read_bytes(&mut self, address: u32, data: &mut [u8]) -> Result<(), MockError> {
+        let range = Self::range(address, data.len())?;
+        data.copy_from_slice(&self.memory[range]);
+        return Ok(());
+    }
+
+    fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), MockError> {
+        let range = Self::range(address, data.len())?;
+        self.memory[range].copy_from_slice(data);
+        return Ok(());
+    }
+}
@@ -1,9 +1,74 @@
 #![no_std]
 
+mod metadata;
+pub use metadata::{Access, DescribedRegister, FieldDescription, RegisterDescription};
+
+mod diff;
+pub use diff::{field_value, FieldChange, FieldDiff};
+
+mod interface;
+pub use interface::{AddressedRegister, RegisterInterface, MockInterface, MockError};
+
+mod async_interface;
+pub use async_interface::AsyncRegisterInterface;
+
+mod shadow;
+pub use shadow::Shadowed;
+
+mod encoding;
+pub use encoding::{Bcd, BcdError, Gray, RawInteger};
+
+mod fixed;
+pub use fixed::{Fixed, UFixed};
+
+pub mod checksum;
+
+#[cfg(feature = "embedded-hal")]
+pub mod hal;
+
+// core items used by #[register] output, core is not reachable by path from 2015 edition crates
+#[doc(hidden)]
+pub mod export {
+    pub use core::ops;
+}
+
 pub trait BitfieldRegister {
     type Data;
+    // smallest unsigned integer holding the register, registers over 16 bytes use Data
+    type IntoBits;
     const REGISTER_SIZE: usize;
     fn data(&self) -> & Self::Data;
+    fn data_mut(&mut self) -> &mut Self::Data;
+    fn into_inner(self) -> Self::Data;
+    fn as_bytes(&self) -> &[u8];
+    fn bits(&self) -> Self::IntoBits;
+    fn from_bits(bits: Self::IntoBits) -> Self;
+    // slice has to be exactly REGISTER_SIZE bytes long
+    fn try_from_slice(slice: &[u8]) -> Result<Self, LengthError> where Self: Sized;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthError {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+// field value rejected by a checking setter or validate()
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    // outside of #[bitfield(range = ...)]
+    OutOfRange { field: &'static str },
+    // wider than the field
+    Overflow { field: &'static str },
+}
+
+impl core::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        return match self {
+            &FieldError::OutOfRange { field } => write!(f, "{}: value out of range", field),
+            &FieldError::Overflow { field } => write!(f, "{}: value does not fit the field", field),
+        };
+    }
 }
 
 pub trait FromBitfield<Array> {
@@ -0,0 +1,75 @@
+// layout of a register as seen by #[register], for documentation and export tools
+
+use super::field_value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    ReadWrite,
+    ReadOnly,
+    WriteOnly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDescription {
+    pub name: &'static str,
+    // doc comment of the field
+    pub description: Option<&'static str>,
+    // span of the field, split fields may leave gaps inside it
+    pub first_bit: usize,
+    pub last_bit: usize,
+    // (first_bit, last_bit) of every piece, least significant first
+    pub ranges: &'static [(usize, usize)],
+    pub access: Access,
+    // #[bitfield(overlay)]: another view of bits owned by other fields
+    pub alias: bool,
+    // endian = "big": the value bytes are stored most significant first
+    pub big_endian: bool,
+}
+
+impl FieldDescription {
+    pub fn bit_width(&self) -> usize {
+        return self.ranges.iter().map(|&(first, last)| last - first + 1).sum();
+    }
+
+    pub fn is_split(&self) -> bool {
+        return self.ranges.len() > 1;
+    }
+
+    // raw field value in bytes of a register, pieces concatenated
+    // big endian fields are byte-swapped back to the value the getter returns
+    // fields wider than 128 bits are truncated to their low 128 bits
+    pub fn value(&self, bytes: &[u8]) -> u128 {
+        let mut value: u128 = 0;
+        let mut offset = 0;
+        for &(first, last) in self.ranges {
+            if offset >= 128 {
+                break;
+            }
+            value |= field_value(bytes, first, last - first + 1) << offset;
+            offset += last - first + 1;
+        }
+        // big endian fields are a whole number of bytes, checked by #[register]
+        if self.big_endian && offset <= 128 {
+            value = value.swap_bytes() >> (128 - offset);
+        }
+        return value;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterDescription {
+    pub name: &'static str,
+    // doc comment of the register struct
+    pub description: Option<&'static str>,
+    pub address: Option<u32>,
+    // in bytes
+    pub size: usize,
+    // Default value, byte 0 holds bits 0..8
+    pub reset: &'static [u8],
+    pub fields: &'static [FieldDescription],
+}
+
+// implemented by #[register] for every register
+pub trait DescribedRegister {
+    const DESCRIPTION: RegisterDescription;
+}
@@ -0,0 +1,99 @@
+use core::ops::{Deref, DerefMut, Range};
+
+use super::{AddressedRegister, AsyncRegisterInterface, BitfieldRegister, DescribedRegister, FieldDiff, RegisterInterface};
+
+// cached copy of a device register, remembers the value last written to the device
+// so flush() writes only the bytes changed since then
+pub struct Shadowed<R> {
+    register: R,
+    flushed: R,
+}
+
+impl<R: BitfieldRegister + Clone> Shadowed<R> {
+    // register is the value currently held by the device
+    pub fn new(register: R) -> Self {
+        return Shadowed { flushed: register.clone(), register };
+    }
+
+    pub fn flushed(&self) -> &R {
+        return &self.flushed;
+    }
+
+    pub fn into_inner(self) -> R {
+        return self.register;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        return self.dirty_range().is_some();
+    }
+
+    // smallest byte range covering every changed byte
+    pub fn dirty_range(&self) -> Option<Range<usize>> {
+        let current = self.register.as_bytes();
+        let flushed = self.flushed.as_bytes();
+        let changed = |i: &usize| current[*i] != flushed[*i];
+
+        let start = (0..current.len()).find(changed)?;
+        let end = (0..current.len()).rev().find(changed)? + 1;
+        return Some(start..end);
+    }
+
+    // the device was written or read by other means and holds the current value now
+    pub fn mark_clean(&mut self) {
+        self.flushed = self.register.clone();
+    }
+
+    // drops changes since the last flush
+    pub fn revert(&mut self) {
+        self.register = self.flushed.clone();
+    }
+}
+
+impl<R: BitfieldRegister + DescribedRegister + Clone> Shadowed<R> {
+    // fields changed since the last flush
+    pub fn dirty_fields(&self) -> FieldDiff<'_> {
+        return FieldDiff::new(R::DESCRIPTION.fields, self.flushed.as_bytes(), self.register.as_bytes());
+    }
+}
+
+impl<R: AddressedRegister + Clone> Shadowed<R> {
+    // reads the register from the device, the result is clean
+    pub fn read<I>(interface: &mut I) -> Result<Self, I::Error>
+        where I: RegisterInterface,
+              R: Default,
+              R::Data: AsMut<[u8]>
+    {
+        return Ok(Shadowed::new(interface.read_register()?));
+    }
+
+    // writes the dirty byte range, nothing when the register is clean
+    pub fn flush<I: RegisterInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        if let Some(range) = self.dirty_range() {
+            interface.write_bytes(R::ADDRESS + range.start as u32, &self.register.as_bytes()[range])?;
+            self.mark_clean();
+        }
+        return Ok(());
+    }
+
+    pub async fn flush_async<I: AsyncRegisterInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        if let Some(range) = self.dirty_range() {
+            interface.write_bytes(R::ADDRESS + range.start as u32, &self.register.as_bytes()[range]).await?;
+            self.mark_clean();
+        }
+        return Ok(());
+    }
+}
+
+impl<R> Deref for Shadowed<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        return &self.register;
+    }
+}
+
+impl<R> DerefMut for Shadowed<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        return &mut self.register;
+    }
+}
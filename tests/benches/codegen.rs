@@ -0,0 +1,79 @@
+// word-wide field access against the bytewise code of #[register(bytewise)]
+// run with cargo +nightly bench -p bitfield-register-tests
+
+#![feature(proc_macro)]
+#![feature(test)]
+
+extern crate bitfield_register;
+extern crate bitfield_register_macro;
+extern crate test;
+
+use bitfield_register::BitfieldRegister;
+use bitfield_register_macro::register;
+use test::{black_box, Bencher};
+
+#[register(size = 8)]
+struct Word {
+    #[bitfield(at = 0)]
+    enable: bool,
+    #[bitfield(from = 3, to = 13)]
+    threshold: u16,
+    #[bitfield(from = 16, to = 47)]
+    counter: u32,
+    #[bitfield(from = 50, to = 61)]
+    offset: u16,
+}
+
+#[register(size = 8, bytewise)]
+struct Bytewise {
+    #[bitfield(at = 0)]
+    enable: bool,
+    #[bitfield(from = 3, to = 13)]
+    threshold: u16,
+    #[bitfield(from = 16, to = 47)]
+    counter: u32,
+    #[bitfield(from = 50, to = 61)]
+    offset: u16,
+}
+
+#[bench]
+fn word_get(b: &mut Bencher) {
+    let reg = Word::from([0xA5; 8]);
+    b.iter(|| {
+        let reg = black_box(&reg);
+        return (reg.get_enable(), reg.get_threshold(), reg.get_counter(), reg.get_offset());
+    });
+}
+
+#[bench]
+fn bytewise_get(b: &mut Bencher) {
+    let reg = Bytewise::from([0xA5; 8]);
+    b.iter(|| {
+        let reg = black_box(&reg);
+        return (reg.get_enable(), reg.get_threshold(), reg.get_counter(), reg.get_offset());
+    });
+}
+
+#[bench]
+fn word_set(b: &mut Bencher) {
+    b.iter(|| {
+        let mut reg = Word::from(black_box([0xA5; 8]));
+        reg.set_enable(black_box(true));
+        reg.set_threshold(black_box(0x5A5));
+        reg.set_counter(black_box(0xDEADBEEF));
+        reg.set_offset(black_box(0xABC));
+        return reg.into_inner();
+    });
+}
+
+#[bench]
+fn bytewise_set(b: &mut Bencher) {
+    b.iter(|| {
+        let mut reg = Bytewise::from(black_box([0xA5; 8]));
+        reg.set_enable(black_box(true));
+        reg.set_threshold(black_box(0x5A5));
+        reg.set_counter(black_box(0xDEADBEEF));
+        reg.set_offset(black_box(0xABC));
+        return reg.into_inner();
+    });
+}
@@ -2,6 +2,8 @@
 
 extern crate bitfield_register;
 extern crate bitfield_register_macro;
+#[cfg(test)]
+extern crate embedded_hal_mock;
 
 
 macro_rules! test_default {
@@ -1276,3 +1278,1429 @@ mod field_6_9 {
         );
     }
 }
+
+mod interface_test {
+    use super::bitfield_register::{AddressedRegister, BitfieldRegister, RegisterInterface, MockInterface, MockError};
+    use super::bitfield_register_macro::register;
+
+    #[register(address = 0x1A)]
+    struct Ctrl {
+        #[bitfield(from = 0, to = 3)]
+        mode: u8,
+        #[bitfield(at = 12)]
+        enable: bool,
+    }
+
+    #[register(address = 0x1F)]
+    struct Status {
+        #[bitfield(from = 0, to = 7)]
+        value: u8,
+    }
+
+    #[test]
+    fn address_test() {
+        assert_eq!(Ctrl::ADDRESS, 0x1A);
+        assert_eq!(Status::ADDRESS, 0x1F);
+    }
+
+    #[test]
+    fn read_register_test() {
+        let mut bus: MockInterface<32> = MockInterface::new();
+        bus.memory_mut()[0x1A] = 0b00000101;
+        bus.memory_mut()[0x1B] = 0b00010000;
+
+        let ctrl: Ctrl = bus.read_register().unwrap();
+        assert_eq!(ctrl.get_mode(), 5);
+        assert_eq!(ctrl.get_enable(), true);
+        assert_eq!(ctrl.data(), &[0b00000101, 0b00010000]);
+    }
+
+    #[test]
+    fn write_register_test() {
+        let mut bus: MockInterface<32> = MockInterface::new();
+
+        let mut ctrl: Ctrl = Default::default();
+        ctrl.set_mode(9);
+        ctrl.set_enable(true);
+        bus.write_register(&ctrl).unwrap();
+
+        assert_eq!(&bus.memory()[0x19..0x1C], &[0, 0b00001001, 0b00010000]);
+    }
+
+    #[test]
+    fn modify_register_test() {
+        let mut bus: MockInterface<32> = MockInterface::new();
+        bus.memory_mut()[0x1A] = 0b11110011;
+
+        bus.modify_register(|ctrl: &mut Ctrl| ctrl.set_enable(true)).unwrap();
+        assert_eq!(&bus.memory()[0x1A..0x1C], &[0b11110011, 0b00010000]);
+
+        bus.modify_register(|ctrl: &mut Ctrl| ctrl.set_mode(0)).unwrap();
+        assert_eq!(&bus.memory()[0x1A..0x1C], &[0b11110000, 0b00010000]);
+    }
+
+    #[test]
+    fn out_of_bounds_test() {
+        let mut bus: MockInterface<0x20> = MockInterface::new();
+        assert!(bus.read_register::<Status>().is_ok());
+
+        let mut bus: MockInterface<0x1B> = MockInterface::new();
+        assert_eq!(bus.read_register::<Status>().err(), Some(MockError::OutOfBounds));
+        assert_eq!(bus.read_register::<Ctrl>().err(), Some(MockError::OutOfBounds));
+        assert_eq!(bus.write_register(&Ctrl::default()), Err(MockError::OutOfBounds));
+    }
+}
+
+mod async_interface_test {
+    use super::bitfield_register::{AsyncRegisterInterface, MockInterface, MockError};
+    use super::bitfield_register_macro::register;
+
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    // busy-polling executor, mock futures are always ready
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[register(address = 0x04)]
+    struct Ctrl {
+        #[bitfield(from = 0, to = 3)]
+        mode: u8,
+        #[bitfield(at = 12)]
+        enable: bool,
+    }
+
+    #[test]
+    fn read_register_test() {
+        let mut bus: MockInterface<8> = MockInterface::from_memory([0, 0, 0, 0, 0b00000101, 0b00010000, 0, 0]);
+
+        let ctrl: Ctrl = block_on(bus.read_register()).unwrap();
+        assert_eq!(ctrl.get_mode(), 5);
+        assert_eq!(ctrl.get_enable(), true);
+    }
+
+    #[test]
+    fn write_register_test() {
+        let mut bus: MockInterface<8> = MockInterface::new();
+
+        let mut ctrl: Ctrl = Default::default();
+        ctrl.set_mode(9);
+        block_on(bus.write_register(&ctrl)).unwrap();
+
+        assert_eq!(bus.memory(), &[0, 0, 0, 0, 0b00001001, 0, 0, 0]);
+    }
+
+    #[test]
+    fn modify_register_test() {
+        let mut bus: MockInterface<8> = MockInterface::from_memory([0, 0, 0, 0, 0b11110011, 0, 0, 0]);
+
+        block_on(bus.modify_register(|ctrl: &mut Ctrl| ctrl.set_enable(true))).unwrap();
+        assert_eq!(bus.memory(), &[0, 0, 0, 0, 0b11110011, 0b00010000, 0, 0]);
+    }
+
+    #[test]
+    fn out_of_bounds_test() {
+        let mut bus: MockInterface<5> = MockInterface::new();
+
+        assert_eq!(block_on(bus.read_register::<Ctrl>()).err(), Some(MockError::OutOfBounds));
+        assert_eq!(block_on(bus.write_register(&Ctrl::default())), Err(MockError::OutOfBounds));
+    }
+}
+
+#[cfg(test)]
+mod hal_test {
+    use super::bitfield_register::RegisterInterface;
+    use super::bitfield_register::hal::{AddressWidth, Error, I2cConfig, I2cInterface, SpiConfig, SpiInterface};
+    use super::bitfield_register_macro::register;
+
+    use embedded_hal_mock::eh1::i2c;
+    use embedded_hal_mock::eh1::spi;
+
+    #[register(address = 0x20)]
+    struct Ctrl {
+        #[bitfield(from = 0, to = 3)]
+        mode: u8,
+        #[bitfield(at = 12)]
+        enable: bool,
+    }
+
+    #[register(address = 0x0F)]
+    struct WhoAmI {
+        #[bitfield(from = 0, to = 7)]
+        id: u8,
+    }
+
+    #[register(address = 0x1234)]
+    struct Wide {
+        #[bitfield(from = 0, to = 7)]
+        value: u8,
+    }
+
+    #[test]
+    fn i2c_read_test() {
+        let expectations = [
+            i2c::Transaction::write_read(0x18, vec![0x20], vec![0b00000101, 0b00010000]),
+        ];
+        let mut device = I2cInterface::new(i2c::Mock::new(&expectations), 0x18);
+
+        let ctrl: Ctrl = device.read_register().unwrap();
+        assert_eq!(ctrl.get_mode(), 5);
+        assert_eq!(ctrl.get_enable(), true);
+
+        device.release().done();
+    }
+
+    #[test]
+    fn i2c_write_test() {
+        let expectations = [
+            i2c::Transaction::transaction_start(0x18),
+            i2c::Transaction::write(0x18, vec![0x20]),
+            i2c::Transaction::write(0x18, vec![0b00001001, 0b00010000]),
+            i2c::Transaction::transaction_end(0x18),
+        ];
+        let mut device = I2cInterface::new(i2c::Mock::new(&expectations), 0x18);
+
+        let mut ctrl: Ctrl = Default::default();
+        ctrl.set_mode(9);
+        ctrl.set_enable(true);
+        device.write_register(&ctrl).unwrap();
+
+        device.release().done();
+    }
+
+    #[test]
+    fn i2c_auto_increment_test() {
+        let expectations = [
+            i2c::Transaction::write_read(0x18, vec![0xA0], vec![0, 0]),
+            i2c::Transaction::write_read(0x18, vec![0x0F], vec![0x33]),
+        ];
+        let config = I2cConfig { address_width: AddressWidth::U8, auto_increment: 0x80 };
+        let mut device = I2cInterface::with_config(i2c::Mock::new(&expectations), 0x18, config);
+
+        device.read_register::<Ctrl>().unwrap();
+        // single byte transfers are sent without the auto increment flag
+        assert_eq!(device.read_register::<WhoAmI>().unwrap().get_id(), 0x33);
+
+        device.release().done();
+    }
+
+    #[test]
+    fn i2c_address_width_test() {
+        let expectations = [
+            i2c::Transaction::write_read(0x50, vec![0x12, 0x34], vec![0xAB]),
+        ];
+        let config = I2cConfig { address_width: AddressWidth::U16, auto_increment: 0 };
+        let mut device = I2cInterface::with_config(i2c::Mock::new(&expectations), 0x50, config);
+
+        assert_eq!(device.read_register::<Wide>().unwrap().get_value(), 0xAB);
+
+        device.release().done();
+    }
+
+    #[test]
+    fn i2c_address_error_test() {
+        let mut device = I2cInterface::new(i2c::Mock::new(&[]), 0x50);
+
+        assert_eq!(device.read_register::<Wide>().err(), Some(Error::Address(0x1234)));
+
+        device.release().done();
+    }
+
+    #[test]
+    fn spi_read_high_test() {
+        let expectations = [
+            spi::Transaction::transaction_start(),
+            spi::Transaction::write_vec(vec![0x8F]),
+            spi::Transaction::read_vec(vec![0x33]),
+            spi::Transaction::transaction_end(),
+            spi::Transaction::transaction_start(),
+            spi::Transaction::write_vec(vec![0x0F]),
+            spi::Transaction::write_vec(vec![0x44]),
+            spi::Transaction::transaction_end(),
+        ];
+        let mut device = SpiInterface::new(spi::Mock::new(&expectations));
+
+        let mut who_am_i: WhoAmI = device.read_register().unwrap();
+        assert_eq!(who_am_i.get_id(), 0x33);
+
+        who_am_i.set_id(0x44);
+        device.write_register(&who_am_i).unwrap();
+
+        device.release().done();
+    }
+
+    #[test]
+    fn spi_write_high_test() {
+        let expectations = [
+            spi::Transaction::transaction_start(),
+            spi::Transaction::write_vec(vec![0x0F]),
+            spi::Transaction::read_vec(vec![0x33]),
+            spi::Transaction::transaction_end(),
+            spi::Transaction::transaction_start(),
+            spi::Transaction::write_vec(vec![0x8F]),
+            spi::Transaction::write_vec(vec![0x33]),
+            spi::Transaction::transaction_end(),
+        ];
+        let mut device = SpiInterface::with_config(spi::Mock::new(&expectations), SpiConfig::write_high());
+
+        device.modify_register(|_: &mut WhoAmI| ()).unwrap();
+
+        device.release().done();
+    }
+
+    #[test]
+    fn spi_auto_increment_test() {
+        let expectations = [
+            spi::Transaction::transaction_start(),
+            spi::Transaction::write_vec(vec![0xE0]),
+            spi::Transaction::read_vec(vec![0b00000101, 0b00010000]),
+            spi::Transaction::transaction_end(),
+            spi::Transaction::transaction_start(),
+            spi::Transaction::write_vec(vec![0x60]),
+            spi::Transaction::write_vec(vec![0b00000101, 0b00010000]),
+            spi::Transaction::transaction_end(),
+        ];
+        let config = SpiConfig { auto_increment: 0x40, ..SpiConfig::read_high() };
+        let mut device = SpiInterface::with_config(spi::Mock::new(&expectations), config);
+
+        let ctrl: Ctrl = device.read_register().unwrap();
+        assert_eq!(ctrl.get_mode(), 5);
+        device.write_register(&ctrl).unwrap();
+
+        device.release().done();
+    }
+}
+
+mod register_params_test {
+    use super::bitfield_register::BitfieldRegister;
+    use super::bitfield_register_macro::register;
+
+    /// Register with a reset value wider than its fields
+    #[register(size = 4, reset = 0x12000005)]
+    struct Reset {
+        /// Mode selection
+        #[bitfield(from = 0, to = 3)]
+        mode: u8,
+        #[bitfield(from = 8, to = 15, access = "read-only")]
+        status: u8,
+        #[bitfield(at = 16, access = "write-only")]
+        trigger: bool,
+    }
+
+    #[test]
+    fn reset_test() {
+        let reg = Reset::default();
+        assert_eq!(Reset::REGISTER_SIZE, 4);
+        assert_eq!(reg.data(), &[0x05, 0x00, 0x00, 0x12]);
+        assert_eq!(reg.get_mode(), 5);
+    }
+
+    #[test]
+    fn access_test() {
+        let mut reg: Reset = From::from([0, 0xAB, 0, 0]);
+        assert_eq!(reg.get_status(), 0xAB);
+
+        reg.set_trigger(true);
+        assert_eq!(reg.data(), &[0, 0xAB, 1, 0]);
+    }
+}
+
+mod bits_test {
+    use super::bitfield_register::BitfieldRegister;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 1)]
+    struct Byte {
+        #[bitfield(from = 0, to = 7)]
+        value: u8,
+    }
+
+    #[register(size = 3, reset = 0x123456)]
+    struct Triple {
+        #[bitfield(from = 4, to = 19)]
+        middle: u16,
+    }
+
+    #[register(size = 8)]
+    struct Long {
+        #[bitfield(at = 63)]
+        top: bool,
+    }
+
+    #[register(size = 16)]
+    struct Wide {
+        #[bitfield(at = 127)]
+        top: bool,
+    }
+
+    #[register(size = 17)]
+    struct Huge {
+        #[bitfield(at = 135)]
+        top: bool,
+    }
+
+    fn trait_bits<R: BitfieldRegister>(reg: &R) -> R::IntoBits {
+        return reg.bits();
+    }
+
+    #[test]
+    fn bits_test() {
+        let byte = Byte::from_bits(0xA5);
+        assert_eq!(byte.get_value(), 0xA5);
+        assert_eq!(byte.bits(), 0xA5u8);
+
+        let triple = Triple::default();
+        assert_eq!(triple.bits(), 0x123456u32);
+        assert_eq!(triple.get_middle(), 0x2345);
+        // bits above the register are dropped
+        assert_eq!(Triple::from_bits(0xFF654321).data(), &[0x21, 0x43, 0x65]);
+
+        let mut long = Long::from_bits(1);
+        long.set_top(true);
+        assert_eq!(long.bits(), 0x8000000000000001u64);
+
+        let wide = Wide::from_bits(1 << 127);
+        assert_eq!(wide.get_top(), true);
+        assert_eq!(trait_bits(&wide), 1u128 << 127);
+
+        let huge = Huge::from_bits([0xFF; 17]);
+        assert_eq!(huge.bits(), [0xFF; 17]);
+        assert_eq!(<Huge as BitfieldRegister>::from_bits([0; 17]).get_top(), false);
+    }
+}
+
+mod raw_access_test {
+    use super::bitfield_register::{BitfieldRegister, LengthError};
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 2)]
+    struct Frame {
+        #[bitfield(from = 0, to = 3)]
+        kind: u8,
+        #[bitfield(from = 8, to = 15)]
+        length: u8,
+    }
+
+    #[test]
+    fn data_mut_test() {
+        let mut frame = Frame::default();
+        frame.data_mut()[1] = 0x20;
+        assert_eq!(frame.get_length(), 0x20);
+
+        frame.data_mut().copy_from_slice(&[0x03, 0x10]);
+        assert_eq!(frame.get_kind(), 3);
+        assert_eq!(frame.as_bytes(), &[0x03, 0x10][..]);
+        assert_eq!(frame.into_inner(), [0x03, 0x10]);
+    }
+
+    #[test]
+    fn try_from_slice_test() {
+        let buffer = [0xA2, 0x08, 0xFF, 0xFF];
+
+        let frame = Frame::try_from_slice(&buffer[..2]).unwrap();
+        assert_eq!(frame.get_kind(), 2);
+        assert_eq!(frame.get_length(), 8);
+
+        assert_eq!(Frame::try_from_slice(&buffer).err(), Some(LengthError { expected: 2, actual: 4 }));
+        assert_eq!(Frame::try_from_slice(&[]).err(), Some(LengthError { expected: 2, actual: 0 }));
+    }
+}
+
+mod view_test {
+    use super::bitfield_register::{BitfieldRegister, LengthError};
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 3)]
+    struct Header {
+        #[bitfield(from = 0, to = 3)]
+        version: u8,
+        #[bitfield(from = 4, to = 19)]
+        length: u16,
+        #[bitfield(at = 23, access = "read-only")]
+        last: bool,
+    }
+
+    #[test]
+    fn ref_test() {
+        let packet = [0xFF, 0x21, 0x43, 0x85, 0xFF];
+
+        let header = HeaderRef::new(&packet, 1).unwrap();
+        assert_eq!(header.get_version(), 1);
+        assert_eq!(header.get_length(), 0x5432);
+        assert_eq!(header.get_last(), true);
+        assert_eq!(header.as_bytes(), &packet[1..4]);
+        assert_eq!(header.to_register().data(), &[0x21, 0x43, 0x85]);
+
+        assert_eq!(HeaderRef::new(&packet, 3).err().map(|err| err.actual), Some(5));
+        assert_eq!(HeaderRef::new(&packet, 6).err().map(|err| err.expected), Some(9));
+    }
+
+    #[test]
+    fn mut_test() {
+        let mut packet = [0xFF, 0x00, 0x00, 0x00, 0xFF];
+
+        {
+            let mut header = HeaderMut::new(&mut packet, 1).unwrap();
+            header.set_version(2);
+            header.set_length(0xABC);
+            assert_eq!(header.get_length(), 0xABC);
+        }
+        assert_eq!(packet, [0xFF, 0xC2, 0xAB, 0x00, 0xFF]);
+
+        let register: Header = From::from([0x03, 0x00, 0x80]);
+        HeaderMut::new(&mut packet, 2).unwrap().write(&register);
+        assert_eq!(packet, [0xFF, 0xC2, 0x03, 0x00, 0x80]);
+
+        assert_eq!(HeaderMut::new(&mut packet, 4).err(), Some(LengthError { expected: 7, actual: 5 }));
+        assert_eq!(HeaderRef::new(&packet, usize::max_value()).err(), Some(LengthError { expected: usize::max_value(), actual: 5 }));
+    }
+}
+
+mod bitwise_test {
+    use super::bitfield_register::BitfieldRegister;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 2, reset = 0x8001)]
+    struct Ctrl {
+        #[bitfield(at = 0)]
+        enable: bool,
+        #[bitfield(from = 6, to = 9)]
+        mode: u8,
+        #[bitfield(at = 15)]
+        irq: bool,
+    }
+
+    #[test]
+    fn mask_test() {
+        assert_eq!(Ctrl::ENABLE_MASK.data(), &[0b00000001, 0b00000000]);
+        assert_eq!(Ctrl::MODE_MASK.data(), &[0b11000000, 0b00000011]);
+        assert_eq!(Ctrl::IRQ_MASK.data(), &[0b00000000, 0b10000000]);
+    }
+
+    #[test]
+    fn operators_test() {
+        let current = Ctrl::default();
+        let mut new_mode = Ctrl::from_bits(0);
+        new_mode.set_mode(0b1010);
+
+        let updated = current & !Ctrl::MODE_MASK | new_mode;
+        assert_eq!(updated.bits(), 0x8281);
+        assert_eq!(updated.get_mode(), 0b1010);
+
+        let changed = updated ^ Ctrl::default();
+        assert_eq!(changed.bits(), 0x0280);
+        assert_eq!((changed & Ctrl::MODE_MASK).bits(), 0x0280);
+        assert_eq!((!Ctrl::from_bits(0x00FF)).bits(), 0xFF00);
+    }
+}
+
+mod diff_test {
+    use super::bitfield_register::FieldChange;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 2)]
+    struct Ctrl {
+        #[bitfield(at = 0)]
+        enable: bool,
+        #[bitfield(from = 4, to = 5)]
+        mode: u8,
+        #[bitfield(from = 7, to = 12)]
+        prescaler: u8,
+    }
+
+    #[test]
+    fn diff_test() {
+        let expected = Ctrl::from_bits(0b0_0000_1001_0001);
+        let actual = Ctrl::from_bits(0b0_0000_1011_0001);
+
+        let changes: Vec<FieldChange> = expected.diff(&actual).collect();
+        assert_eq!(changes, vec![FieldChange { name: "mode", old: 0b01, new: 0b11, bit_width: 2 }]);
+        assert_eq!(changes[0].to_string(), "mode: 0b01 -> 0b11");
+
+        assert_eq!(expected.diff(&expected.clone()).count(), 0);
+
+        let other = Ctrl::from_bits(0b1_1000_0000_0000);
+        let names: Vec<String> = expected.diff(&other).map(|change| change.to_string()).collect();
+        assert_eq!(names, vec!["enable: 0b1 -> 0b0", "mode: 0b01 -> 0b00", "prescaler: 0b000001 -> 0b110000"]);
+    }
+}
+
+mod shadow_test {
+    use super::bitfield_register::{BitfieldRegister, MockInterface, Shadowed};
+    use super::bitfield_register_macro::register;
+
+    #[register(address = 0x02, size = 4)]
+    struct Config {
+        #[bitfield(from = 0, to = 3)]
+        mode: u8,
+        #[bitfield(from = 8, to = 15)]
+        threshold: u8,
+        #[bitfield(from = 16, to = 23)]
+        hysteresis: u8,
+        #[bitfield(at = 31)]
+        enable: bool,
+    }
+
+    #[test]
+    fn dirty_test() {
+        let mut config = Shadowed::new(Config::default());
+        assert_eq!(config.is_dirty(), false);
+        assert_eq!(config.dirty_range(), None);
+
+        config.set_threshold(10);
+        assert_eq!(config.dirty_range(), Some(1..2));
+
+        config.set_hysteresis(3);
+        config.set_threshold(0);
+        assert_eq!(config.dirty_range(), Some(2..3));
+
+        config.set_enable(true);
+        let fields: Vec<&str> = config.dirty_fields().map(|change| change.name).collect();
+        assert_eq!(fields, vec!["hysteresis", "enable"]);
+        assert_eq!(config.dirty_range(), Some(2..4));
+
+        config.revert();
+        assert_eq!(config.is_dirty(), false);
+        assert_eq!(config.get_hysteresis(), 0);
+    }
+
+    #[test]
+    fn flush_test() {
+        // bytes the shadow does not know about show which bytes were written
+        let mut bus: MockInterface<8> = MockInterface::from_memory([0xEE; 8]);
+        let mut config = Shadowed::new(Config::default());
+
+        config.flush(&mut bus).unwrap();
+        assert_eq!(bus.memory(), &[0xEE; 8]);
+
+        config.set_threshold(0x12);
+        config.set_hysteresis(0x34);
+        config.flush(&mut bus).unwrap();
+        assert_eq!(bus.memory(), &[0xEE, 0xEE, 0xEE, 0x12, 0x34, 0xEE, 0xEE, 0xEE]);
+        assert_eq!(config.is_dirty(), false);
+        assert_eq!(config.flushed().data(), &[0x00, 0x12, 0x34, 0x00]);
+    }
+
+    #[test]
+    fn read_test() {
+        let mut bus: MockInterface<8> = MockInterface::from_memory([0, 0, 0x05, 0x20, 0, 0x80, 0, 0]);
+        let mut config: Shadowed<Config> = Shadowed::read(&mut bus).unwrap();
+        assert_eq!(config.get_mode(), 5);
+        assert_eq!(config.get_enable(), true);
+
+        config.set_mode(7);
+        config.flush(&mut bus).unwrap();
+        assert_eq!(bus.memory(), &[0, 0, 0x07, 0x20, 0, 0x80, 0, 0]);
+    }
+}
+
+mod range_test {
+    use super::bitfield_register::FieldError;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 2)]
+    struct Clock {
+        #[bitfield(from = 0, to = 3, range = 1..=12)]
+        divider: u8,
+        #[bitfield(from = 4, to = 6, range = 0..5)]
+        source: u8,
+        #[bitfield(from = 8, to = 15)]
+        trim: u8,
+    }
+
+    #[test]
+    fn try_set_test() {
+        let mut clock = Clock::from_bits(0x0001);
+        assert_eq!(clock.try_set_divider(12), Ok(()));
+        assert_eq!(clock.get_divider(), 12);
+
+        assert_eq!(clock.try_set_divider(13), Err(FieldError::OutOfRange { field: "divider" }));
+        assert_eq!(clock.try_set_divider(0), Err(FieldError::OutOfRange { field: "divider" }));
+        assert_eq!(clock.get_divider(), 12);
+
+        assert_eq!(clock.try_set_source(5).unwrap_err().to_string(), "source: value out of range");
+
+        // unchecked setter keeps its behaviour
+        clock.set_divider(15);
+        assert_eq!(clock.get_divider(), 15);
+    }
+
+    #[test]
+    fn validate_test() {
+        assert!(Clock::from_bits(0xFF41).is_valid());
+
+        let errors: Vec<FieldError> = Clock::default().validate().collect();
+        assert_eq!(errors, vec![FieldError::OutOfRange { field: "divider" }]);
+
+        let errors: Vec<FieldError> = Clock::from_bits(0x007F).validate().collect();
+        assert_eq!(errors, vec![
+            FieldError::OutOfRange { field: "divider" },
+            FieldError::OutOfRange { field: "source" },
+        ]);
+
+        let mut packet = [0x00, 0x0D, 0x00];
+        assert_eq!(ClockRef::new(&packet, 1).unwrap().validate().count(), 1);
+        assert!(ClockMut::new(&mut packet, 1).unwrap().try_set_divider(3).is_ok());
+        assert_eq!(packet, [0x00, 0x03, 0x00]);
+    }
+}
+
+mod checked_setter_test {
+    use super::bitfield_register::{FieldError, FromBitfield, IntoBitfield};
+    use super::bitfield_register_macro::register;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Raw3 {
+        Value(u8),
+    }
+
+    impl FromBitfield<[u8;1]> for Raw3 {
+        fn from_bitfield(array: [u8;1]) -> Self {
+            return Raw3::Value(array[0]);
+        }
+    }
+
+    impl IntoBitfield<[u8;1]> for Raw3 {
+        fn into_bitfield(self) -> [u8;1] {
+            return match self {
+                Raw3::Value(value) => [value],
+            };
+        }
+    }
+
+    #[register(size = 4)]
+    struct Wrapping {
+        #[bitfield(from = 0, to = 6)]
+        level: u8,
+        #[bitfield(from = 7, to = 9)]
+        raw: Raw3,
+        #[bitfield(from = 10, to = 27)]
+        count: u32,
+        #[bitfield(from = 28, to = 31, range = 2..=9)]
+        gain: u8,
+    }
+
+    #[register(size = 2, checked)]
+    struct Checked {
+        #[bitfield(from = 0, to = 6)]
+        level: u8,
+        #[bitfield(from = 8, to = 15)]
+        full: u8,
+    }
+
+    #[test]
+    fn wrapping_test() {
+        let mut reg = Wrapping::from_bits(0);
+        reg.set_level(200);
+        assert_eq!(reg.get_level(), 72);
+
+        assert_eq!(reg.try_set_level(200), Err(FieldError::Overflow { field: "level" }));
+        assert_eq!(reg.get_level(), 72);
+        assert_eq!(reg.try_set_level(127), Ok(()));
+        assert_eq!(reg.get_level(), 127);
+
+        reg.set_level_saturating(200);
+        assert_eq!(reg.get_level(), 127);
+        reg.set_level_saturating(5);
+        assert_eq!(reg.get_level(), 5);
+    }
+
+    #[test]
+    fn width_test() {
+        let mut reg = Wrapping::from_bits(0);
+
+        // u32 field of 18 bits, the value does not fit its 3 bytes either
+        assert_eq!(reg.try_set_count(1 << 24), Err(FieldError::Overflow { field: "count" }));
+        assert_eq!(reg.try_set_count(1 << 18), Err(FieldError::Overflow { field: "count" }));
+        assert_eq!(reg.try_set_count((1 << 18) - 1), Ok(()));
+        assert_eq!(reg.get_count(), (1 << 18) - 1);
+        reg.set_count_saturating(u32::max_value());
+        assert_eq!(reg.get_count(), (1 << 18) - 1);
+
+        // other types are checked on their bytes
+        assert_eq!(reg.try_set_raw(Raw3::Value(8)), Err(FieldError::Overflow { field: "raw" }));
+        assert_eq!(reg.try_set_raw(Raw3::Value(7)), Ok(()));
+        assert_eq!(reg.get_raw(), Raw3::Value(7));
+
+        assert_eq!(reg.try_set_gain(16), Err(FieldError::OutOfRange { field: "gain" }));
+        assert_eq!(reg.try_set_gain(1), Err(FieldError::OutOfRange { field: "gain" }));
+        assert_eq!(reg.try_set_gain(9), Ok(()));
+        assert_eq!(FieldError::Overflow { field: "gain" }.to_string(), "gain: value does not fit the field");
+    }
+
+    #[test]
+    fn checked_test() {
+        let mut reg = Checked::from_bits(0);
+        assert_eq!(reg.set_level(200), Err(FieldError::Overflow { field: "level" }));
+        assert_eq!(reg.set_level(100), Ok(()));
+        assert_eq!(reg.get_level(), 100);
+
+        reg.set_level_wrapping(200);
+        assert_eq!(reg.get_level(), 72);
+
+        assert_eq!(reg.set_full(255), Ok(()));
+        reg.set_full_saturating(255);
+        assert_eq!(reg.bits(), 0xFF48);
+    }
+}
+
+mod wide_register_test {
+    use super::bitfield_register::{BitfieldRegister, DescribedRegister};
+    use super::bitfield_register_macro::register;
+
+    // 64 bytes, e.g. an EEPROM page
+    #[register(size = 64)]
+    struct Page {
+        #[bitfield(from = 0, to = 7)]
+        header: u8,
+        #[bitfield(from = 250, to = 265)]
+        across: u16,
+        #[bitfield(at = 300)]
+        flag: bool,
+        #[bitfield(from = 480, to = 503)]
+        serial: u32,
+        #[bitfield(at = 511)]
+        last: bool,
+    }
+
+    #[test]
+    fn wide_test() {
+        assert_eq!(Page::REGISTER_SIZE, 64);
+
+        let mut page = Page::default();
+        page.set_flag(true);
+        assert_eq!(page.data()[37], 0b00010000);
+        assert_eq!(page.get_flag(), true);
+
+        page.set_across(0xABCD);
+        assert_eq!(page.get_across(), 0xABCD);
+        assert_eq!(&page.data()[31..34], &[0x34, 0xAF, 0x02]);
+
+        page.set_serial(0x123456);
+        assert_eq!(&page.data()[60..63], &[0x56, 0x34, 0x12]);
+        page.set_last(true);
+        assert_eq!(page.data()[63], 0x80);
+        assert_eq!(page.get_header(), 0);
+
+        assert_eq!(page.try_set_serial(1 << 24).is_err(), true);
+        assert_eq!(page.bits().len(), 64);
+    }
+
+    #[test]
+    fn wide_metadata_test() {
+        let fields = Page::DESCRIPTION.fields;
+        assert_eq!((fields[2].first_bit, fields[2].last_bit), (300, 300));
+        assert_eq!((fields[4].first_bit, fields[4].last_bit), (511, 511));
+
+        let mut changed = Page::default();
+        changed.set_flag(true);
+        let names: Vec<&str> = Page::default().diff(&changed).map(|change| change.name).collect();
+        assert_eq!(names, vec!["flag"]);
+    }
+}
+
+mod split_field_test {
+    use super::bitfield_register::{BitfieldRegister, DescribedRegister};
+    use super::bitfield_register_macro::register;
+
+    // PLL divider with its low and high nibbles in different bytes
+    #[register(size = 2)]
+    struct Pll {
+        #[bitfield(bits = [0..=3, 8..=11])]
+        divider: u8,
+        #[bitfield(from = 4, to = 7)]
+        mode: u8,
+        #[bitfield(bits = [15, 12..=14])]
+        rotated: u8,
+    }
+
+    #[test]
+    fn split_test() {
+        let mut pll = Pll::default();
+        pll.set_divider(0xA5);
+        assert_eq!(pll.data(), &[0x05, 0x0A]);
+        assert_eq!(pll.get_divider(), 0xA5);
+
+        pll.set_mode(0xF);
+        pll.set_rotated(0b1011);
+        assert_eq!(pll.data(), &[0xF5, 0xDA]);
+        assert_eq!(pll.get_rotated(), 0b1011);
+        assert_eq!(pll.get_divider(), 0xA5);
+
+        assert_eq!(pll.try_set_rotated(0x10).is_err(), true);
+        assert_eq!(Pll::DIVIDER_MASK.bits(), 0x0F0F);
+        assert_eq!(Pll::ROTATED_MASK.bits(), 0xF000);
+    }
+
+    #[test]
+    fn split_metadata_test() {
+        let divider = &Pll::DESCRIPTION.fields[0];
+        assert_eq!(divider.ranges, &[(0, 3), (8, 11)]);
+        assert_eq!((divider.first_bit, divider.last_bit), (0, 11));
+        assert_eq!(divider.bit_width(), 8);
+
+        let mut changed = Pll::default();
+        changed.set_divider(0x30);
+        let changes: Vec<(&str, u128, u128)> = Pll::default().diff(&changed).map(|change| (change.name, change.old, change.new)).collect();
+        assert_eq!(changes, vec![("divider", 0, 0x30)]);
+    }
+}
+
+mod overlay_test {
+    use super::bitfield_register::DescribedRegister;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 1)]
+    struct Status {
+        /// all bits at once
+        #[bitfield(from = 0, to = 7, overlay)]
+        raw: u8,
+        #[bitfield(from = 0, to = 3)]
+        count: u8,
+        #[bitfield(from = 4, to = 7)]
+        flags: u8,
+    }
+
+    #[test]
+    fn overlay_test() {
+        let mut status = Status::default();
+        status.set_raw(0x35);
+        assert_eq!((status.get_count(), status.get_flags()), (5, 3));
+
+        status.set_flags(0xA);
+        assert_eq!(status.get_raw(), 0xA5);
+    }
+
+    #[test]
+    fn overlay_metadata_test() {
+        let fields = Status::DESCRIPTION.fields;
+        let aliases: Vec<(&str, bool)> = fields.iter().map(|field| (field.name, field.alias)).collect();
+        assert_eq!(aliases, vec![("raw", true), ("count", false), ("flags", false)]);
+
+        let mut changed = Status::default();
+        changed.set_raw(0x10);
+        let names: Vec<&str> = Status::default().diff(&changed).map(|change| change.name).collect();
+        assert_eq!(names, vec!["flags"]);
+    }
+}
+
+mod mode_test {
+    use super::bitfield_register::{DescribedRegister, FieldError, MockInterface};
+    use super::bitfield_register_macro::register;
+
+    /// Timer channel
+    #[register(address = 0x02, size = 2, reset = 0x8000)]
+    #[mode(from = 0, to = 1)]
+    enum Channel {
+        /// Input capture
+        Capture {
+            #[bitfield(from = 2, to = 5)]
+            filter: u8,
+            #[bitfield(at = 6)]
+            falling: bool,
+        } = 1,
+        /// Output compare
+        Compare {
+            #[bitfield(from = 2, to = 4)]
+            action: u8,
+            #[bitfield(at = 15)]
+            preload: bool,
+        } = 2,
+        Disabled = 0,
+    }
+
+    #[test]
+    fn decode_test() {
+        match Channel::decode([0b0101_0101, 0]) {
+            Ok(Channel::Capture(capture)) => {
+                assert_eq!(capture.get_filter(), 0b0101);
+                assert_eq!(capture.get_falling(), true);
+                assert_eq!(capture.get_mode(), 1);
+            },
+            _ => panic!("expected capture mode"),
+        }
+
+        assert_eq!(Channel::decode([0b1110, 0x80]).map(|channel| channel.mode()).ok(), Some(2));
+        assert_eq!(Channel::decode([0, 0]).map(|channel| channel.mode()).ok(), Some(0));
+        assert_eq!(Channel::decode([3, 0]).err(), Some(FieldError::OutOfRange { field: "mode" }));
+    }
+
+    #[test]
+    fn encode_test() {
+        let mut compare = ChannelCompare::default();
+        assert_eq!(compare.get_mode(), 2);
+        assert_eq!(compare.get_preload(), true);
+        compare.set_action(0b101);
+
+        let channel = Channel::from(compare);
+        assert_eq!(channel.encode(), [0b10110, 0x80]);
+
+        // the mode field follows the variant, whatever the bytes say
+        let channel = Channel::Capture(ChannelCapture::from([0xFF, 0xFF]));
+        assert_eq!(channel.encode(), [0xFD, 0xFF]);
+
+        assert_eq!(ChannelCapture::DESCRIPTION.name, "ChannelCapture");
+        assert_eq!(ChannelCapture::DESCRIPTION.fields[0].name, "mode");
+    }
+
+    #[test]
+    fn interface_test() {
+        let mut bus = MockInterface::<4>::new();
+        let mut capture = ChannelCapture::default();
+        capture.set_filter(3);
+        Channel::from(capture).write(&mut bus).unwrap();
+        assert_eq!(bus.memory(), &[0, 0, 0b1101, 0x80]);
+
+        match Channel::read(&mut bus) {
+            Ok(Ok(Channel::Capture(capture))) => assert_eq!(capture.get_filter(), 3),
+            _ => panic!("expected capture mode"),
+        }
+
+        bus.memory_mut()[2] = 3;
+        assert_eq!(Channel::read(&mut bus).unwrap().is_err(), true);
+    }
+}
+
+mod bytewise_test {
+    use super::bitfield_register::BitfieldRegister;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 9)]
+    struct Word {
+        #[bitfield(from = 3, to = 13)]
+        threshold: u16,
+        #[bitfield(from = 16, to = 47)]
+        counter: u32,
+        #[bitfield(from = 50, to = 69)]
+        wide: u32,
+    }
+
+    #[register(size = 9, bytewise)]
+    struct Bytewise {
+        #[bitfield(from = 3, to = 13)]
+        threshold: u16,
+        #[bitfield(from = 16, to = 47)]
+        counter: u32,
+        #[bitfield(from = 50, to = 69)]
+        wide: u32,
+    }
+
+    #[test]
+    fn same_layout_test() {
+        for &fill in &[0x00u8, 0xFF, 0xA5, 0x5A] {
+            let word = Word::from([fill; 9]);
+            let bytewise = Bytewise::from([fill; 9]);
+            assert_eq!(word.get_threshold(), bytewise.get_threshold());
+            assert_eq!(word.get_counter(), bytewise.get_counter());
+            assert_eq!(word.get_wide(), bytewise.get_wide());
+
+            let mut word = word;
+            let mut bytewise = bytewise;
+            word.set_threshold(0x5A5);
+            bytewise.set_threshold(0x5A5);
+            word.set_counter(0xDEADBEEF);
+            bytewise.set_counter(0xDEADBEEF);
+            word.set_wide(0xFFFFF);
+            bytewise.set_wide(0xFFFFF);
+            assert_eq!(word.data(), bytewise.data());
+        }
+    }
+}
+
+mod endian_test {
+    use super::bitfield_register::{BitfieldRegister, FieldChange};
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 4)]
+    struct Frame {
+        #[bitfield(from = 0, to = 7)]
+        kind: u8,
+        #[bitfield(from = 8, to = 23, endian = "big")]
+        length: u16,
+        #[bitfield(from = 12, to = 27, endian = "big", overlay)]
+        unaligned: u16,
+        #[bitfield(from = 24, to = 31, endian = "big")]
+        flags: u8,
+    }
+
+    #[register(size = 4, bytewise)]
+    struct BytewiseFrame {
+        #[bitfield(from = 8, to = 23, endian = "big")]
+        length: u16,
+        #[bitfield(bits = [24..=31, 0..=7], endian = "big")]
+        split: u16,
+    }
+
+    #[test]
+    fn big_endian_test() {
+        let mut frame = Frame::default();
+        frame.set_length(0x1234);
+        assert_eq!(frame.data(), &[0x00, 0x12, 0x34, 0x00]);
+        assert_eq!(frame.get_length(), 0x1234);
+
+        frame.set_flags(0xAB);
+        assert_eq!(frame.get_flags(), 0xAB);
+        assert_eq!(frame.get_unaligned(), 0x41B3);
+        assert_eq!(frame.try_set_length(0x1234).is_ok(), true);
+
+        // diff reports the values the getters return
+        let changes: Vec<FieldChange> = Frame::default().diff(&frame).collect();
+        assert_eq!(changes[0].name, "length");
+        assert_eq!((changes[0].old, changes[0].new), (0, 0x1234));
+        assert_eq!((changes[1].name, changes[1].new), ("flags", 0xAB));
+
+        let mut frame = BytewiseFrame::default();
+        frame.set_length(0x1234);
+        frame.set_split(0xABCD);
+        assert_eq!(frame.data(), &[0xCD, 0x12, 0x34, 0xAB]);
+        assert_eq!((frame.get_length(), frame.get_split()), (0x1234, 0xABCD));
+        let changes: Vec<(&str, u128)> = BytewiseFrame::default().diff(&frame).map(|change| (change.name, change.new)).collect();
+        assert_eq!(changes, vec![("length", 0x1234), ("split", 0xABCD)]);
+    }
+}
+
+mod encoding_test {
+    use super::bitfield_register::{Bcd, BcdError, BitfieldRegister, Gray};
+    use super::bitfield_register_macro::register;
+
+    // time of an RTC chip
+    #[register(size = 3)]
+    struct Time {
+        #[bitfield(from = 0, to = 6)]
+        seconds: Bcd<u8>,
+        #[bitfield(from = 8, to = 14)]
+        minutes: Bcd<u8>,
+        #[bitfield(from = 16, to = 21, range = Bcd::<u8>::from_raw(0x00)..=Bcd::<u8>::from_raw(0x23))]
+        hours: Bcd<u8>,
+    }
+
+    #[register(size = 2)]
+    struct Encoder {
+        #[bitfield(from = 0, to = 9)]
+        position: Gray<u16>,
+    }
+
+    #[test]
+    fn bcd_test() {
+        let mut time = Time::default();
+        time.set_seconds(Bcd::encode(59).unwrap());
+        time.set_minutes(Bcd::encode(7).unwrap());
+        time.set_hours(Bcd::encode(23).unwrap());
+        assert_eq!(time.data(), &[0x59, 0x07, 0x23]);
+        assert_eq!(time.get_seconds().decode(), Ok(59));
+        assert_eq!(time.is_valid(), true);
+
+        time.set_hours(Bcd::encode(24).unwrap());
+        assert_eq!(time.is_valid(), false);
+
+        let time = Time::from([0x5A, 0x00, 0x00]);
+        assert_eq!(time.get_seconds().decode(), Err(BcdError::InvalidDigit));
+        assert_eq!(time.get_seconds().raw(), 0x5A);
+
+        assert_eq!(Bcd::<u8>::encode(100), Err(BcdError::Overflow));
+        assert_eq!(Bcd::<u16>::encode(9999).map(Bcd::raw), Ok(0x9999));
+        assert_eq!(Bcd::<u32>::from_raw(0x12345678).decode(), Ok(12345678));
+
+        // 0x80 needs bit 7, outside of the 7-bit seconds field
+        let mut time = Time::default();
+        assert_eq!(time.try_set_seconds(Bcd::encode(80).unwrap()).is_err(), true);
+    }
+
+    #[test]
+    fn gray_test() {
+        let mut encoder = Encoder::default();
+        encoder.set_position(Gray::encode(511));
+        assert_eq!(encoder.get_position().raw(), 0b100000000);
+        assert_eq!(encoder.get_position().decode(), 511);
+
+        for value in 0..1024u16 {
+            assert_eq!(Gray::encode(value).decode(), value);
+            let next = Gray::encode(value + 1).raw();
+            assert_eq!((Gray::encode(value).raw() ^ next).count_ones(), 1);
+        }
+        assert_eq!(Gray::<u32>::from_raw(0x80000000).decode(), 0xFFFFFFFF);
+    }
+}
+
+mod fixed_test {
+    use super::bitfield_register::{BitfieldRegister, Fixed, UFixed};
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 4)]
+    struct Sensor {
+        // Q4.12 temperature
+        #[bitfield(from = 0, to = 15)]
+        temperature: Fixed<4, 12>,
+        // UQ8.8 gain
+        #[bitfield(from = 16, to = 31)]
+        gain: UFixed<8, 8>,
+    }
+
+    #[register(size = 2)]
+    struct Offset {
+        // Q2.4 in the middle of the register
+        #[bitfield(from = 3, to = 8)]
+        offset: Fixed<2, 4>,
+    }
+
+    #[test]
+    fn fixed_field_test() {
+        let mut sensor = Sensor::default();
+        sensor.set_temperature(Fixed::from_f32(-1.5));
+        sensor.set_gain(UFixed::from_f64(2.25));
+        assert_eq!(sensor.data(), &[0x00, 0xE8, 0x40, 0x02]);
+        assert_eq!(sensor.get_temperature().to_f32(), -1.5);
+        assert_eq!(f64::from(sensor.get_gain()), 2.25);
+
+        let mut offset = Offset::default();
+        offset.set_offset(Fixed::from_f32(-0.25));
+        assert_eq!(offset.bits(), 0b111100 << 3);
+        assert_eq!(offset.get_offset().to_f32(), -0.25);
+        assert_eq!(offset.try_set_offset(Fixed::from_f32(1.9375)).is_ok(), true);
+        assert_eq!(offset.get_offset(), Fixed::<2, 4>::MAX);
+    }
+
+    #[test]
+    fn fixed_conversion_test() {
+        type Q4_12 = Fixed<4, 12>;
+        assert_eq!(Q4_12::MAX.to_f64(), 8.0 - 1.0 / 4096.0);
+        assert_eq!(Q4_12::MIN.to_f64(), -8.0);
+        assert_eq!(Q4_12::from_f64(100.0), Q4_12::MAX);
+        assert_eq!(Q4_12::from_f64(-100.0), Q4_12::MIN);
+        assert_eq!(Q4_12::from_f64(0.1).raw(), 410);
+        assert_eq!(Q4_12::from_f64(-0.1).raw(), -410);
+        assert_eq!(Q4_12::from_raw(0xFFFF).raw(), -1);
+
+        assert_eq!(UFixed::<8, 8>::from_f64(-1.0), UFixed::MIN);
+        assert_eq!(UFixed::<8, 8>::from_f64(1000.0).raw(), 0xFFFF);
+        assert_eq!(UFixed::<0, 32>::MAX.raw(), 0xFFFFFFFF);
+        assert_eq!(Fixed::<16, 16>::from_f64(-2.5).raw(), -0x28000);
+    }
+
+    #[test]
+    fn fixed_arithmetic_test() {
+        type Q4_12 = Fixed<4, 12>;
+        let a = Q4_12::from_f32(1.5);
+        let b = Q4_12::from_f32(-2.25);
+        assert_eq!((a + b).to_f32(), -0.75);
+        assert_eq!((a - b).to_f32(), 3.75);
+        assert_eq!((a * b).to_f32(), -3.375);
+        assert_eq!((b / a).to_f32(), -1.5);
+        assert_eq!((-b).to_f32(), 2.25);
+        assert_eq!(a < b, false);
+
+        // wraps like the hardware
+        assert_eq!(Q4_12::MAX + Q4_12::from_raw(1), Q4_12::MIN);
+        assert_eq!(UFixed::<8, 8>::from_f32(0.5) - UFixed::from_f32(1.0), UFixed::from_f32(255.5));
+        assert_eq!(UFixed::<8, 8>::from_f32(1.5) * UFixed::from_f32(3.0), UFixed::from_f32(4.5));
+    }
+}
+
+mod scaled_test {
+    use super::bitfield_register::{BitfieldRegister, FieldError};
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 2)]
+    struct Thermometer {
+        /// Temperature, 0.5 °C steps from -40 °C
+        #[bitfield(from = 0, to = 9, scale = 0.5, offset = -40.0)]
+        temperature: u16,
+        #[bitfield(from = 10, to = 15, scale = 100, access = "read-only")]
+        voltage: u8,
+    }
+
+    #[test]
+    fn scaled_test() {
+        let mut thermometer = Thermometer::default();
+        assert_eq!(thermometer.temperature_scaled(), -40.0);
+
+        assert_eq!(thermometer.set_temperature_scaled(21.3), Ok(()));
+        assert_eq!(thermometer.get_temperature(), 123);
+        assert_eq!(thermometer.temperature_scaled(), 21.5);
+
+        assert_eq!(thermometer.set_temperature_scaled(-40.2), Ok(()));
+        assert_eq!(thermometer.get_temperature(), 0);
+        assert_eq!(thermometer.set_temperature_scaled(-40.3), Err(FieldError::OutOfRange { field: "temperature" }));
+        assert_eq!(thermometer.set_temperature_scaled(471.5), Ok(()));
+        assert_eq!(thermometer.get_temperature(), 1023);
+        assert_eq!(thermometer.set_temperature_scaled(471.8), Err(FieldError::OutOfRange { field: "temperature" }));
+        assert_eq!(thermometer.set_temperature_scaled(::std::f32::NAN).is_err(), true);
+        assert_eq!(thermometer.get_temperature(), 1023);
+
+        let thermometer = Thermometer::from_bits(33 << 10);
+        assert_eq!(thermometer.voltage_scaled(), 3300.0);
+    }
+}
+
+mod checksum_test {
+    use super::bitfield_register::checksum::{crc16, crc8};
+    use super::bitfield_register::BitfieldRegister;
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 4)]
+    #[checksum(kind = "crc8", over = 0..=23, at = 24..=31)]
+    struct Frame {
+        #[bitfield(from = 0, to = 7)]
+        command: u8,
+        #[bitfield(from = 8, to = 23)]
+        argument: u16,
+    }
+
+    #[register(size = 1)]
+    #[checksum(kind = "parity_even", over = 0..=6, at = 7)]
+    struct Byte {
+        #[bitfield(from = 0, to = 6)]
+        data: u8,
+    }
+
+    #[register(size = 4)]
+    #[checksum(kind = "crc16", over = 0..=15, at = 16..=31)]
+    struct Word {
+        #[bitfield(from = 0, to = 15)]
+        data: u16,
+    }
+
+    #[register(size = 4)]
+    #[checksum(kind = "crc16", over = 0..=15, at = 16..=31, endian = "big")]
+    struct Packet {
+        #[bitfield(from = 0, to = 15)]
+        data: u16,
+    }
+
+    #[test]
+    fn crc_test() {
+        assert_eq!(crc8(b"123456789"), 0xF4);
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+
+        let mut frame = Frame::default();
+        frame.set_command(0x12);
+        frame.set_argument(0x5634);
+        assert_eq!(frame.data(), &[0x12, 0x34, 0x56, crc8(&[0x12, 0x34, 0x56])]);
+        assert_eq!(frame.verify_checksum(), true);
+
+        let received = Frame::from([0x12, 0x34, 0x57, frame.data()[3]]);
+        assert_eq!(received.verify_checksum(), false);
+
+        let mut word = Word::from([0xAB, 0xCD, 0, 0]);
+        assert_eq!(word.verify_checksum(), false);
+        word.seal();
+        assert_eq!(word.bits(), (crc16(&[0xAB, 0xCD]) as u32) << 16 | 0xCDAB);
+        assert_eq!(word.verify_checksum(), true);
+
+        // CRC-16/CCITT-FALSE of "12" is 0x3DBA, transmitted 0x3D first
+        let mut packet = Packet::default();
+        packet.set_data(0x3231);
+        assert_eq!(packet.data(), &[0x31, 0x32, 0x3D, 0xBA]);
+        assert_eq!(Packet::from([0x31, 0x32, 0x3D, 0xBA]).verify_checksum(), true);
+        assert_eq!(Packet::from([0x31, 0x32, 0xBA, 0x3D]).verify_checksum(), false);
+    }
+
+    #[test]
+    fn parity_test() {
+        let mut byte = Byte::default();
+        byte.set_data(0b0000111);
+        assert_eq!(byte.data(), &[0b10000111]);
+        byte.set_data(0b0000011);
+        assert_eq!(byte.data(), &[0b00000011]);
+        assert_eq!(byte.verify_checksum(), true);
+        assert_eq!(Byte::from([0b10000011]).verify_checksum(), false);
+    }
+
+    #[test]
+    fn view_checksum_test() {
+        let mut buffer = [0u8; 6];
+        {
+            let mut frame = FrameMut::new(&mut buffer, 2).unwrap();
+            frame.set_command(0xA0);
+        }
+        assert_eq!(buffer[5], crc8(&[0xA0, 0, 0]));
+        assert_eq!(FrameRef::new(&buffer, 2).unwrap().verify_checksum(), true);
+    }
+}
+
+mod helpers_test {
+    use super::bitfield_register::{BitfieldRegister, FieldError};
+    use super::bitfield_register_macro::register;
+
+    #[register(size = 1)]
+    struct Interrupts {
+        #[bitfield(at = 0)]
+        pending: bool,
+        #[bitfield(at = 1, access = "read-only")]
+        overrun: bool,
+        #[bitfield(from = 4, to = 7)]
+        count: u8,
+    }
+
+    #[register(size = 1, checked)]
+    struct Counter {
+        #[bitfield(at = 0)]
+        enabled: bool,
+        #[bitfield(from = 1, to = 3)]
+        count: u8,
+    }
+
+    // is_valid and set_ready_bit already exist, those helpers are left out
+    #[register(size = 1)]
+    struct Sample {
+        #[bitfield(at = 0)]
+        valid: bool,
+        #[bitfield(at = 1)]
+        ready: bool,
+        #[bitfield(at = 2)]
+        ready_bit: bool,
+    }
+
+    #[test]
+    fn flag_test() {
+        let mut interrupts = Interrupts::default();
+        interrupts.set_pending_bit();
+        assert_eq!(interrupts.is_pending(), true);
+        interrupts.toggle_pending();
+        assert_eq!(interrupts.data(), &[0]);
+        interrupts.toggle_pending();
+        assert_eq!(interrupts.data(), &[1]);
+        interrupts.clear_pending();
+        assert_eq!(interrupts.is_pending(), false);
+
+        assert_eq!(Interrupts::from([0b10]).is_overrun(), true);
+        assert_eq!(InterruptsRef::new(&[0b11], 0).unwrap().is_pending(), true);
+    }
+
+    #[test]
+    fn update_test() {
+        let mut interrupts = Interrupts::default();
+        interrupts.update_count(|count| count + 1);
+        interrupts.update_count(|count| count * 5);
+        assert_eq!(interrupts.get_count(), 5);
+
+        let mut counter = Counter::default();
+        counter.set_enabled_bit();
+        assert_eq!(counter.update_count(|count| count + 7), Ok(()));
+        assert_eq!(counter.update_count(|count| count + 1), Err(FieldError::Overflow { field: "count" }));
+        assert_eq!(counter.data(), &[0b1111]);
+        counter.toggle_enabled();
+        assert_eq!(counter.is_enabled(), false);
+    }
+
+    #[test]
+    fn name_clash_test() {
+        let mut sample = Sample::default();
+        sample.set_valid_bit();
+        sample.toggle_ready();
+        assert_eq!(sample.data(), &[0b011]);
+        assert_eq!(sample.is_valid(), true);
+
+        sample.set_ready_bit(true);
+        sample.clear_valid();
+        assert_eq!(sample.data(), &[0b110]);
+        assert_eq!((sample.get_valid(), sample.is_valid()), (false, true));
+    }
+}